@@ -0,0 +1,211 @@
+#!/usr/bin/env python3
+"""Write the FBX 6.1 test fixtures, as binary and ASCII files.
+
+    python3 scripts/fbx6_fixture.py tests/fixtures
+
+The fixture is a two quads mesh with two materials, each using
+one of the two embedded textures through `LayerElementTexture`.
+"""
+import base64
+import os
+import struct
+import sys
+import zlib
+
+
+def png(rgba):
+    raw = b'\x00' + bytes(rgba)
+
+    def chunk(kind, data):
+        crc = zlib.crc32(kind + data) & 0xffffffff
+        return struct.pack('>I', len(data)) + kind + data + struct.pack('>I', crc)
+
+    header = struct.pack('>IIBBBBB', 1, 1, 8, 6, 0, 0, 0)
+    return (b'\x89PNG\r\n\x1a\n' + chunk(b'IHDR', header)
+            + chunk(b'IDAT', zlib.compress(raw)) + chunk(b'IEND', b''))
+
+
+# Nodes are (name, attributes, children), attributes are (type, value)
+# with the binary FBX type codes.
+def N(name, attributes=(), children=()):
+    return (name, list(attributes), list(children))
+
+
+def S(value): return ('S', value)
+def I(value): return ('I', value)
+def D(value): return ('D', value)
+def ints(values): return ('i', values)
+def doubles(values): return ('d', values)
+
+
+def obj(name, cls):
+    return S(f'{name}\x00\x01{cls}')
+
+
+def prop(name, kind, *values):
+    return N('Property', [S(name), S(kind), S('')] + list(values))
+
+
+def layer_element(name, mapping, data):
+    return N(name, [I(0)], [
+        N('Version', [I(101)]),
+        N('Name', [S('')]),
+        N('MappingInformationType', [S(mapping)]),
+        N('ReferenceInformationType', [S('IndexToDirect')]),
+    ] + data)
+
+
+def texture(name):
+    return N('Texture', [obj(name, 'Texture'), S('TextureVideoClip')], [
+        N('Type', [S('TextureVideoClip')]),
+        N('Version', [I(202)]),
+        N('Media', [obj(name, 'Video')]),
+        N('FileName', [S(f'{name}.png')]),
+        N('RelativeFilename', [S(f'{name}.png')]),
+    ])
+
+
+def video(name, color):
+    return N('Video', [obj(name, 'Video'), S('Clip')], [
+        N('Type', [S('Clip')]),
+        N('Filename', [S(f'{name}.png')]),
+        N('RelativeFilename', [S(f'{name}.png')]),
+        N('Content', [('R', png(color))]),
+    ])
+
+
+def material(name, color):
+    return N('Material', [obj(name, 'Material'), S('')], [
+        N('Version', [I(102)]),
+        N('ShadingModel', [S('lambert')]),
+        N('Properties60', [], [prop('DiffuseColor', 'ColorRGB', *map(D, color))]),
+    ])
+
+
+def connect(source, destination):
+    return N('Connect', [S('OO'), obj(*source), obj(*destination)])
+
+
+mesh = N('Model', [obj('Quads', 'Model'), S('Mesh')], [
+    N('Version', [I(232)]),
+    N('Properties60', [], [
+        prop('Lcl Translation', 'Lcl Translation', D(0.0), D(1.0), D(0.0)),
+        prop('Lcl Rotation', 'Lcl Rotation', D(0.0), D(0.0), D(0.0)),
+        prop('Lcl Scaling', 'Lcl Scaling', D(1.0), D(1.0), D(1.0)),
+    ]),
+    N('Vertices', [doubles([0.0, 0, 0, 1, 0, 0, 2, 0, 0, 0, 1, 0, 1, 1, 0, 2, 1, 0])]),
+    N('PolygonVertexIndex', [ints([0, 1, 4, ~3, 1, 2, 5, ~4])]),
+    N('GeometryVersion', [I(124)]),
+    layer_element('LayerElementMaterial', 'ByPolygon', [N('Materials', [ints([0, 1])])]),
+    # Textures are connected in the reverse order of the materials, the first
+    # material uses the second texture and the other way around.
+    layer_element('LayerElementTexture', 'ByPolygon', [N('TextureId', [ints([1, 0])])]),
+    N('Layer', [I(0)], [N('Version', [I(100)])] + [
+        N('LayerElement', [], [N('Type', [S(kind)]), N('TypedIndex', [I(0)])])
+        for kind in ['LayerElementMaterial', 'LayerElementTexture']
+    ]),
+])
+nodes = [
+    N('FBXHeaderExtension', [], [N('FBXHeaderVersion', [I(1003)]), N('FBXVersion', [I(6100)])]),
+    N('Objects', [], [
+        mesh,
+        material('Red', [1.0, 0.0, 0.0]),
+        material('Green', [0.0, 1.0, 0.0]),
+        # A second object with the same name, which is skipped.
+        material('Green', [0.0, 0.0, 1.0]),
+        texture('RedTexture'),
+        texture('GreenTexture'),
+        video('RedTexture', [255, 0, 0, 255]),
+        video('GreenTexture', [0, 255, 0, 255]),
+        N('GlobalSettings', [], [N('Version', [I(1000)]), N('Properties60', [], [
+            prop('UpAxis', 'int', I(1)), prop('UpAxisSign', 'int', I(1)),
+            prop('FrontAxis', 'int', I(2)), prop('FrontAxisSign', 'int', I(1)),
+            prop('CoordAxis', 'int', I(0)), prop('CoordAxisSign', 'int', I(1)),
+            prop('UnitScaleFactor', 'double', D(1.0)),
+        ])]),
+    ]),
+    N('Connections', [], [
+        connect(('Quads', 'Model'), ('Scene', 'Model')),
+        connect(('Red', 'Material'), ('Quads', 'Model')),
+        connect(('Green', 'Material'), ('Quads', 'Model')),
+        connect(('GreenTexture', 'Texture'), ('Quads', 'Model')),
+        connect(('RedTexture', 'Texture'), ('Quads', 'Model')),
+        connect(('RedTexture', 'Video'), ('RedTexture', 'Texture')),
+        connect(('GreenTexture', 'Video'), ('GreenTexture', 'Texture')),
+    ]),
+    N('Takes', [], [N('Current', [S('')])]),
+]
+
+
+def binary_attribute(kind, value):
+    if kind in 'SR':
+        data = value.encode('latin-1') if isinstance(value, str) else value
+        return kind.encode() + struct.pack('<I', len(data)) + data
+    if kind in 'ID':
+        return kind.encode() + struct.pack({'I': '<i', 'D': '<d'}[kind], value)
+    data = struct.pack('<%d%s' % (len(value), kind), *value)
+    return kind.encode() + struct.pack('<III', len(value), 0, len(data)) + data
+
+
+def binary_node(node, offset):
+    name, attributes, children = node
+    attributes = b''.join(binary_attribute(*a) for a in attributes)
+    start = offset + 13 + len(name) + len(attributes)
+    body = b''
+    for child in children:
+        body += binary_node(child, start + len(body))
+    if children:
+        body += b'\x00' * 13
+    end = start + len(body)
+    header = struct.pack('<IIIB', end, len(node[1]), len(attributes), len(name))
+    return header + name.encode() + attributes + body
+
+
+def binary(nodes):
+    out = b'Kaydara FBX Binary  \x00\x1a\x00' + struct.pack('<I', 6100)
+    for node in nodes:
+        out += binary_node(node, len(out))
+    out += b'\x00' * 13
+    # The footer, as written by the FBX SDK.
+    out += b'\x00' * 16 + struct.pack('<I', 0) + b'\x00' * 4 + struct.pack('<I', 6100)
+    out += b'\x00' * 120 + bytes.fromhex('f85a8c6adef5d97eece90ce3758f290b')
+    return out
+
+
+def ascii_value(kind, value, indent):
+    if kind == 'S':
+        if '\x00\x01' in value:
+            name, cls = value.split('\x00\x01')
+            value = f'{cls}::{name}'
+        return '"' + value.replace('"', '&quot;') + '"'
+    if kind == 'R':
+        return f',\n{indent}\t"{base64.b64encode(value).decode()}"'
+    if kind in 'id':
+        return ','.join(str(v) for v in value)
+    return str(value)
+
+
+def ascii_node(node, indent=''):
+    name, attributes, children = node
+    values = ', '.join(ascii_value(*a, indent) for a in attributes).replace(', ,', ',')
+    line = f'{indent}{name}: {values}'
+    if not children:
+        return [line]
+    lines = [line + ' {']
+    for child in children:
+        lines += ascii_node(child, indent + '\t')
+    return lines + [indent + '}']
+
+
+def ascii(nodes):
+    lines = ['; FBX 6.1.0 project file', '']
+    for node in nodes:
+        lines += ascii_node(node)
+    return '\n'.join(lines) + '\n'
+
+
+directory = sys.argv[1] if len(sys.argv) > 1 else 'tests/fixtures'
+with open(os.path.join(directory, 'v6100_binary.fbx'), 'wb') as file:
+    file.write(binary(nodes))
+with open(os.path.join(directory, 'v6100_ascii.fbx'), 'w') as file:
+    file.write(ascii(nodes))
@@ -0,0 +1,695 @@
+//! Import FBX `AnimationStack`s as bevy `AnimationClip`s.
+
+// a bit of trivia on how animations are encoded in FBX:
+// - An AnimationStack is a "take", what bevy calls an AnimationClip.
+// - A stack is made of AnimationLayers that are blended together,
+//   we only read the first (base) layer.
+// - Layers contain AnimationCurveNodes, each curve node is connected to a
+//   single property of a Model (such as "Lcl Translation") and has up to
+//   three AnimationCurves connected to it, one per axis ("d|X", "d|Y", "d|Z").
+// - AnimationCurves are list of key times and values.
+// - Each key of a curve has attributes (`KeyAttrFlags`, `KeyAttrDataFloat`)
+//   telling how the curve goes to the next key: constant, linear or cubic.
+//   Keys with the same attributes share them, `KeyAttrRefCount` is how many
+//   consecutive keys use each attribute.
+//
+// Since the FBX transform is way more complex than bevy's (see `fbx_transform.rs`)
+// we can't just convert the curves into keyframes. Instead, we sample all curves
+// at the key times and frames of the stack (see `sample_times`), and compute the
+// bevy local transform for each sample in the same way we do it for static transforms.
+use std::{collections::BTreeSet, iter};
+
+use anyhow::{Context, Result};
+use bevy::{
+    animation::{AnimationClip, EntityPath, Keyframes, VariableCurve},
+    math::DVec3,
+    prelude::{Name, Quat, Vec3},
+    utils::HashMap,
+};
+use fbxcel_dom::v7400::{
+    object::{ObjectHandle, ObjectId, TypedObjectHandle},
+    Document,
+};
+
+use crate::{
+    data::{FbxDiagnostic, FbxObject},
+    fbx_transform::{FbxNodeTransformInfo, FbxTransform, LclValues},
+};
+
+/// FBX time unit, in ticks per second.
+const FBX_TICKS_PER_SECOND: f64 = 46_186_158_000.0;
+
+/// Frame rate of files with the default `TimeMode`.
+pub(crate) const DEFAULT_FRAME_RATE: f64 = 30.0;
+
+/// Time between the sample before a constant interpolation step and the step.
+const STEP_TICKS: i64 = 46_186_158; // 1ms
+
+/// Largest change of Euler angles between two samples, in degrees.
+///
+/// Quaternions go the shortest way between two rotations, so samples
+/// must be less than 180° apart for the rotation to go the authored way.
+const MAX_ROTATION_STEP: f64 = 90.0;
+
+/// `KeyAttrFlags` bits, from the FBX SDK `FbxAnimCurveDef`.
+const CONSTANT: i32 = 0x0000_0002;
+const CUBIC: i32 = 0x0000_0008;
+const CONSTANT_NEXT: i32 = 0x0000_0100;
+const WEIGHTED_RIGHT: i32 = 0x0100_0000;
+const WEIGHTED_NEXT_LEFT: i32 = 0x0200_0000;
+
+/// Tangent weight of keys without weighted tangents.
+const DEFAULT_WEIGHT: f32 = 1.0 / 3.0;
+
+/// How a curve goes from a key to the next one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Interpolation {
+    /// Keep the value of the key, or the value of the next key if `next`.
+    Constant {
+        next: bool,
+    },
+    Linear,
+    /// Cubic Bézier curve, with tangents given as slopes, in value per second,
+    /// and weights, as a fraction of the time between the two keys.
+    Cubic {
+        right_slope: f32,
+        next_left_slope: f32,
+        right_weight: f32,
+        next_left_weight: f32,
+    },
+}
+impl Interpolation {
+    /// Read a key attribute, `data` being its four `KeyAttrDataFloat`.
+    fn from_attribute(flags: i32, data: &[f32]) -> Self {
+        if flags & CONSTANT != 0 {
+            return Interpolation::Constant {
+                next: flags & CONSTANT_NEXT != 0,
+            };
+        }
+        if flags & CUBIC == 0 {
+            return Interpolation::Linear;
+        }
+        // Weights are two integers in 1/9999th, packed in the third float.
+        let weights = data[2].to_bits();
+        let weight = |weighted: bool, weight: u32| match weighted {
+            true => (weight as f32 / 9999.0).clamp(0.0, 1.0),
+            false => DEFAULT_WEIGHT,
+        };
+        Interpolation::Cubic {
+            right_slope: data[0],
+            next_left_slope: data[1],
+            right_weight: weight(flags & WEIGHTED_RIGHT != 0, weights & 0xffff),
+            next_left_weight: weight(flags & WEIGHTED_NEXT_LEFT != 0, weights >> 16),
+        }
+    }
+}
+
+/// A single `AnimationCurve`.
+struct Curve<'a> {
+    times: &'a [i64],
+    values: &'a [f32],
+    /// The interpolation of each key, empty if the curve has no key attributes.
+    interpolations: Vec<Interpolation>,
+}
+impl<'a> Curve<'a> {
+    fn from_object(object: ObjectHandle<'a>) -> Option<Self> {
+        let node = object.node();
+        let child = |name: &str| node.first_child_by_name(name)?.attributes().first();
+        let times = child("KeyTime")?.get_arr_i64()?;
+        let values = child("KeyValueFloat")?.get_arr_f32()?;
+        if times.len() != values.len() || times.is_empty() {
+            return None;
+        }
+        let interpolations = || {
+            let flags = child("KeyAttrFlags")?.get_arr_i32()?;
+            let data = child("KeyAttrDataFloat")?.get_arr_f32()?;
+            let ref_counts = child("KeyAttrRefCount")?.get_arr_i32()?;
+            let mut interpolations = Vec::with_capacity(times.len());
+            for (i, &ref_count) in ref_counts.iter().enumerate() {
+                let attribute =
+                    Interpolation::from_attribute(*flags.get(i)?, data.get(i * 4..i * 4 + 4)?);
+                let ref_count = usize::try_from(ref_count).ok()?;
+                interpolations.extend(iter::repeat_n(attribute, ref_count));
+            }
+            Some(interpolations)
+        };
+        Some(Curve {
+            times,
+            values,
+            interpolations: interpolations().unwrap_or_default(),
+        })
+    }
+    /// The value of the curve at `time`.
+    fn sample(&self, time: i64) -> f64 {
+        let next = self.times.partition_point(|t| *t <= time);
+        let key = match next {
+            0 => return self.values[0] as f64,
+            n if n == self.times.len() => return self.values[n - 1] as f64,
+            n => n - 1,
+        };
+        let (t0, t1) = (self.times[key], self.times[key + 1]);
+        let (v0, v1) = (self.values[key] as f64, self.values[key + 1] as f64);
+        let stride = (time - t0) as f64 / (t1 - t0) as f64;
+        match self.interpolation(key) {
+            Interpolation::Constant { next: false } => v0,
+            Interpolation::Constant { next: true } => v1,
+            Interpolation::Linear => v0 + (v1 - v0) * stride,
+            Interpolation::Cubic {
+                right_slope,
+                next_left_slope,
+                right_weight,
+                next_left_weight,
+            } => {
+                let duration = (t1 - t0) as f64 / FBX_TICKS_PER_SECOND;
+                let (w0, w1) = (right_weight as f64, next_left_weight as f64);
+                let times = [0.0, w0, 1.0 - w1, 1.0];
+                let values = [
+                    v0,
+                    v0 + right_slope as f64 * w0 * duration,
+                    v1 - next_left_slope as f64 * w1 * duration,
+                    v1,
+                ];
+                // Find the Bézier parameter at `stride` by bisection,
+                // the curve time always increases with the parameter.
+                let (mut low, mut high) = (0.0, 1.0);
+                for _ in 0..32 {
+                    let middle = (low + high) / 2.0;
+                    if bezier(times, middle) < stride {
+                        low = middle;
+                    } else {
+                        high = middle;
+                    }
+                }
+                bezier(values, (low + high) / 2.0)
+            }
+        }
+    }
+    fn interpolation(&self, key: usize) -> Interpolation {
+        let interpolation = self.interpolations.get(key).copied();
+        interpolation.unwrap_or(Interpolation::Linear)
+    }
+    /// The times at which the curve value jumps, due to constant interpolation.
+    fn steps(&self) -> impl Iterator<Item = i64> + '_ {
+        (0..self.times.len() - 1).filter_map(|key| match self.interpolation(key) {
+            Interpolation::Constant { next: false } => Some(self.times[key + 1]),
+            Interpolation::Constant { next: true } => Some(self.times[key]),
+            _ => None,
+        })
+    }
+}
+
+/// The cubic Bézier curve with the `points` control points at `t`.
+fn bezier(points: [f64; 4], t: f64) -> f64 {
+    let u = 1.0 - t;
+    let [p0, p1, p2, p3] = points;
+    u * u * u * p0 + 3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t * p3
+}
+
+/// The three axis curves of an animated vector property.
+#[derive(Default)]
+struct Vec3Curves<'a>([Option<Curve<'a>>; 3]);
+impl<'a> Vec3Curves<'a> {
+    fn sample(&self, time: i64, default: DVec3) -> DVec3 {
+        let mut value = default;
+        for (axis, curve) in self.0.iter().enumerate() {
+            if let Some(curve) = curve {
+                value[axis] = curve.sample(time);
+            }
+        }
+        value
+    }
+    fn times(&self) -> impl Iterator<Item = i64> + '_ {
+        self.0
+            .iter()
+            .flatten()
+            .flat_map(|c| c.times.iter().copied())
+    }
+    fn steps(&self) -> impl Iterator<Item = i64> + '_ {
+        self.0.iter().flatten().flat_map(Curve::steps)
+    }
+    /// The sum of the changes of each axis between `start` and `end`.
+    fn change(&self, start: i64, end: i64) -> f64 {
+        let change = |curve: &Curve| (curve.sample(end) - curve.sample(start)).abs();
+        self.0.iter().flatten().map(change).sum()
+    }
+}
+
+/// The animated `Lcl` properties of a single node.
+#[derive(Default)]
+struct NodeCurves<'a> {
+    translation: Vec3Curves<'a>,
+    rotation: Vec3Curves<'a>,
+    scaling: Vec3Curves<'a>,
+}
+impl<'a> NodeCurves<'a> {
+    fn sample(&self, time: i64, default: LclValues) -> LclValues {
+        LclValues {
+            translation: self.translation.sample(time, default.translation),
+            rotation: self.rotation.sample(time, default.rotation),
+            scaling: self.scaling.sample(time, default.scaling),
+        }
+    }
+    fn times(&self) -> impl Iterator<Item = i64> + '_ {
+        let translation = self.translation.times();
+        let rotation = self.rotation.times();
+        translation.chain(rotation).chain(self.scaling.times())
+    }
+    fn steps(&self) -> impl Iterator<Item = i64> + '_ {
+        let translation = self.translation.steps();
+        let rotation = self.rotation.steps();
+        translation.chain(rotation).chain(self.scaling.steps())
+    }
+}
+
+/// The times to sample the curves at, so that bevy interpolating linearly
+/// between the samples follows the FBX curves closely.
+///
+/// Beside the key times, this samples:
+/// - every frame at `frame_rate`, for non-linear curves,
+/// - right before constant interpolation steps, so that they don't become ramps,
+/// - between frames where a rotation changes by more than [`MAX_ROTATION_STEP`].
+fn sample_times(curves: &HashMap<ObjectId, NodeCurves>, frame_rate: f64) -> Vec<i64> {
+    let key_times: BTreeSet<i64> = curves.values().flat_map(NodeCurves::times).collect();
+    let steps: BTreeSet<i64> = curves.values().flat_map(NodeCurves::steps).collect();
+    let frame = FBX_TICKS_PER_SECOND / frame_rate;
+    // `start + (end - start) * part / parts`, without overflowing.
+    let lerp = |start: i64, end: i64, part: i64, parts: i64| {
+        start + ((end - start) as f64 * part as f64 / parts as f64) as i64
+    };
+
+    let mut frames = Vec::new();
+    for (&start, &end) in key_times.iter().zip(key_times.iter().skip(1)) {
+        let parts = ((end - start) as f64 / frame).ceil().max(1.0) as i64;
+        frames.extend((0..parts).map(|part| lerp(start, end, part, parts)));
+        let before_step = end - STEP_TICKS.min((end - start) / 2);
+        if steps.contains(&end) && lerp(start, end, parts - 1, parts) < before_step {
+            frames.push(before_step);
+        }
+    }
+    frames.extend(key_times.last());
+
+    let mut times = Vec::with_capacity(frames.len());
+    times.extend(frames.first());
+    for (&start, &end) in frames.iter().zip(frames.iter().skip(1)) {
+        let change = |curves: &NodeCurves| curves.rotation.change(start, end);
+        let change = curves.values().map(change).fold(0.0, f64::max);
+        let parts = (change / MAX_ROTATION_STEP).ceil().max(1.0) as i64;
+        times.extend((1..=parts).map(|part| lerp(start, end, part, parts)));
+    }
+    times.dedup();
+    times
+}
+
+/// Read the curves of the base layer of `stack`, for nodes in `hierarchy`.
+fn stack_curves<'a>(
+    stack: ObjectHandle<'a>,
+    hierarchy: &HashMap<ObjectId, FbxObject>,
+    diagnostics: &mut Vec<FbxDiagnostic>,
+) -> HashMap<ObjectId, NodeCurves<'a>> {
+    let sources_named = |object: ObjectHandle<'a>, node_name: &'static str| {
+        object
+            .source_objects()
+            .filter_map(|obj| Some((obj.label(), obj.object_handle()?)))
+            .filter(move |(_, obj)| obj.node().name() == node_name)
+    };
+    let mut curves: HashMap<ObjectId, NodeCurves> = HashMap::new();
+    let mut layers = sources_named(stack, "AnimationLayer");
+    let Some((_, layer)) = layers.next() else {
+        return curves;
+    };
+    if layers.next().is_some() {
+        FbxDiagnostic::warning("Animation stack has several layers, only reading the first one")
+            .with_object(stack.object_id(), stack.name())
+            .report(diagnostics);
+    }
+    for (_, curve_node) in sources_named(layer, "AnimationCurveNode") {
+        let animated = curve_node
+            .destination_objects()
+            .filter_map(|obj| Some((obj.label()?, obj.object_id())))
+            .find(|(_, id)| hierarchy.contains_key(id));
+        let Some((property, node_id)) = animated else {
+            continue;
+        };
+        let node_curves = curves.entry(node_id).or_default();
+        let vec_curves = match property {
+            "Lcl Translation" => &mut node_curves.translation,
+            "Lcl Rotation" => &mut node_curves.rotation,
+            "Lcl Scaling" => &mut node_curves.scaling,
+            _ => continue,
+        };
+        for (label, curve) in sources_named(curve_node, "AnimationCurve") {
+            let axis = match label {
+                Some("d|X") => 0,
+                Some("d|Y") => 1,
+                Some("d|Z") => 2,
+                _ => continue,
+            };
+            vec_curves.0[axis] = Curve::from_object(curve);
+        }
+    }
+    curves
+}
+
+/// Static information on a node of the hierarchy, used to compute
+/// the animated transforms.
+struct AnimatedNode<'a> {
+    transform_info: FbxNodeTransformInfo,
+    lcl: LclValues,
+    curves: Option<&'a NodeCurves<'a>>,
+    path: EntityPath,
+    /// Whether this node or one of its ancestors is animated.
+    animated: bool,
+    children: Vec<ObjectId>,
+}
+
+/// Keyframes of a single animated node.
+#[derive(Default)]
+struct NodeKeyframes {
+    translations: Vec<Vec3>,
+    rotations: Vec<Quat>,
+    scales: Vec<Vec3>,
+}
+
+/// Convert a FBX `AnimationStack` into a bevy `AnimationClip`.
+///
+/// The first element of the [`EntityPath`]s of the resulting clip is `root_name`,
+/// meaning that the `AnimationPlayer` should be added to the FBX scene root entity.
+///
+/// Translations are multiplied by `unit_scale`, and curves are sampled
+/// at least once per frame at `frame_rate`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn load_animation_stack(
+    doc: &Document,
+    stack: ObjectHandle,
+    root_name: &Name,
+    roots: &[ObjectId],
+    hierarchy: &HashMap<ObjectId, FbxObject>,
+    unit_scale: f32,
+    frame_rate: f64,
+    diagnostics: &mut Vec<FbxDiagnostic>,
+) -> Result<AnimationClip> {
+    let curves = stack_curves(stack, hierarchy, diagnostics);
+    let mut times = sample_times(&curves, frame_rate);
+    let Some(&start_time) = times.first() else {
+        return Ok(AnimationClip::default());
+    };
+    let seconds = |time: i64| ((time - start_time) as f64 / FBX_TICKS_PER_SECOND) as f32;
+    // Bevy can't interpolate between keyframes with the same timestamp.
+    times.dedup_by_key(|time| seconds(*time));
+
+    // Collect the static data of the hierarchy once, so that
+    // we don't read it again for every key time.
+    let mut nodes = HashMap::new();
+    let mut to_visit: Vec<_> = roots
+        .iter()
+        .map(|id| (*id, vec![root_name.clone()], false))
+        .collect();
+    while let Some((id, mut path, parent_animated)) = to_visit.pop() {
+        let Some(node) = hierarchy.get(&id) else {
+            continue;
+        };
+        let model = id.to_object_handle(doc).map(|obj| obj.get_typed());
+        let Some(TypedObjectHandle::Model(model)) = model else {
+            continue;
+        };
+        let model = *model;
+        path.push(node.entity_name(id));
+        let node_curves = curves.get(&id);
+        let animated = parent_animated || node_curves.is_some();
+        let children = node.children.clone();
+        to_visit.extend(children.iter().map(|c| (*c, path.clone(), animated)));
+        let info = AnimatedNode {
+            transform_info: FbxNodeTransformInfo::from_node(model)?,
+            lcl: LclValues::from_node(model)?,
+            curves: node_curves,
+            path: EntityPath { parts: path },
+            animated,
+            children,
+        };
+        nodes.insert(id, info);
+    }
+
+    let mut keyframes: HashMap<ObjectId, NodeKeyframes> = HashMap::new();
+    for &time in &times {
+        let mut to_visit: Vec<(ObjectId, Option<FbxTransform>)> =
+            roots.iter().map(|id| (*id, None)).collect();
+        while let Some((id, parent)) = to_visit.pop() {
+            let Some(node) = nodes.get(&id) else {
+                continue;
+            };
+            let lcl = match node.curves {
+                Some(curves) => curves.sample(time, node.lcl),
+                None => node.lcl,
+            };
+            let info = node.transform_info.with_lcl(lcl);
+            let transform = FbxTransform::from_fbxtrans(info, parent);
+            to_visit.extend(node.children.iter().map(|c| (*c, Some(transform))));
+            if !node.animated {
+                continue;
+            }
+            let local = transform.as_local_transform(parent.map(|p| p.global));
+            let node_keyframes = keyframes.entry(id).or_default();
+            let mut rotation = local.rotation;
+            // Samples are less than 180° apart, so the closest of the two
+            // equivalent quaternions is the one the rotation goes to.
+            if let Some(previous) = node_keyframes.rotations.last() {
+                if previous.dot(rotation) < 0.0 {
+                    rotation = -rotation;
+                }
+            }
+            node_keyframes
+                .translations
+                .push(local.translation * unit_scale);
+            node_keyframes.rotations.push(rotation);
+            node_keyframes.scales.push(local.scale);
+        }
+    }
+    let timestamps: Vec<f32> = times.iter().map(|t| seconds(*t)).collect();
+
+    let mut clip = AnimationClip::default();
+    for (id, node_keyframes) in keyframes {
+        let path = &nodes
+            .get(&id)
+            .context("Animated node not in hierarchy")?
+            .path;
+        let curve = |keyframes| VariableCurve {
+            keyframe_timestamps: timestamps.clone(),
+            keyframes,
+        };
+        let NodeKeyframes {
+            translations,
+            rotations,
+            scales,
+        } = node_keyframes;
+        clip.add_curve_to_path(path.clone(), curve(Keyframes::Translation(translations)));
+        clip.add_curve_to_path(path.clone(), curve(Keyframes::Rotation(rotations)));
+        clip.add_curve_to_path(path.clone(), curve(Keyframes::Scale(scales)));
+    }
+    Ok(clip)
+}
+
+/// Whether `object` is an `AnimationStack`.
+pub(crate) fn is_animation_stack(object: &ObjectHandle) -> bool {
+    object.node().name() == "AnimationStack"
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use fbxcel_dom::v7400::Loader;
+
+    use super::*;
+    use crate::{ascii, utils::fbx_extend::GlobalSettingsExt};
+
+    /// A node spinning a full turn around Z in a second, and moving by one
+    /// unit along X at half a second, at 30 frames per second.
+    const SPINNER: &[u8] = br#"
+FBXHeaderExtension:  {
+    FBXVersion: 7400
+}
+GlobalSettings:  {
+    Version: 1000
+    Properties70:  {
+        P: "TimeMode", "enum", "", "",6
+    }
+}
+Documents:  {
+    Count: 1
+    Document: 1, "", "Scene" {
+        RootNode: 0
+    }
+}
+Definitions:  {
+    ObjectType: "Model" {
+        PropertyTemplate: "FbxNode" {
+            Properties70:  {
+                P: "RotationOrder", "enum", "", "",0
+                P: "InheritType", "enum", "", "",1
+                P: "RotationOffset", "Vector3D", "Vector", "",0,0,0
+                P: "RotationPivot", "Vector3D", "Vector", "",0,0,0
+                P: "ScalingOffset", "Vector3D", "Vector", "",0,0,0
+                P: "ScalingPivot", "Vector3D", "Vector", "",0,0,0
+                P: "PreRotation", "Vector3D", "Vector", "",0,0,0
+                P: "PostRotation", "Vector3D", "Vector", "",0,0,0
+                P: "Lcl Translation", "Vector3D", "Vector", "",0,0,0
+                P: "Lcl Rotation", "Vector3D", "Vector", "",0,0,0
+                P: "Lcl Scaling", "Vector3D", "Vector", "",1,1,1
+            }
+        }
+    }
+}
+Objects:  {
+    Model: 10, "Model::Spinner", "Null" {
+        Version: 232
+    }
+    AnimationStack: 20, "AnimStack::Spin", "" {
+    }
+    AnimationLayer: 30, "AnimLayer::Base", "" {
+    }
+    AnimationCurveNode: 40, "AnimCurveNode::R", "" {
+    }
+    AnimationCurve: 41, "AnimCurve::", "" {
+        KeyTime: *2 {
+            a: 0,46186158000
+        }
+        KeyValueFloat: *2 {
+            a: 0,360
+        }
+        KeyAttrFlags: *1 {
+            a: 24836
+        }
+        KeyAttrDataFloat: *4 {
+            a: 0,0,218434821,0
+        }
+        KeyAttrRefCount: *1 {
+            a: 2
+        }
+    }
+    AnimationCurveNode: 50, "AnimCurveNode::T", "" {
+    }
+    AnimationCurve: 51, "AnimCurve::", "" {
+        KeyTime: *3 {
+            a: 0,23093079000,46186158000
+        }
+        KeyValueFloat: *3 {
+            a: 0,1,1
+        }
+        KeyAttrFlags: *1 {
+            a: 2
+        }
+        KeyAttrDataFloat: *4 {
+            a: 0,0,218434821,0
+        }
+        KeyAttrRefCount: *1 {
+            a: 3
+        }
+    }
+}
+Connections:  {
+    C: "OO",10,0
+    C: "OO",30,20
+    C: "OO",40,30
+    C: "OO",50,30
+    C: "OP",40,10, "Lcl Rotation"
+    C: "OP",41,40, "d|Z"
+    C: "OP",50,10, "Lcl Translation"
+    C: "OP",51,50, "d|X"
+}
+"#;
+
+    fn curve<'a>(values: &'a [f32], interpolation: Interpolation) -> Curve<'a> {
+        Curve {
+            times: &[0, FBX_TICKS_PER_SECOND as i64],
+            values,
+            interpolations: vec![interpolation],
+        }
+    }
+
+    #[test]
+    fn interpolations() {
+        let quarter = FBX_TICKS_PER_SECOND as i64 / 4;
+        let constant = curve(&[0.0, 1.0], Interpolation::Constant { next: false });
+        assert_eq!(constant.sample(quarter), 0.0);
+        let next = curve(&[0.0, 1.0], Interpolation::Constant { next: true });
+        assert_eq!(next.sample(quarter), 1.0);
+        let linear = curve(&[0.0, 1.0], Interpolation::Linear);
+        assert_eq!(linear.sample(quarter), 0.25);
+
+        // Flat tangents ease in and out.
+        let flags = CUBIC | WEIGHTED_RIGHT | WEIGHTED_NEXT_LEFT;
+        let weights = f32::from_bits(3333 << 16 | 3333);
+        let cubic = Interpolation::from_attribute(flags, &[0.0, 0.0, weights, 0.0]);
+        let cubic = curve(&[0.0, 1.0], cubic);
+        assert!((cubic.sample(quarter) - 0.15625).abs() < 1e-3);
+        assert!((cubic.sample(quarter * 2) - 0.5).abs() < 1e-6);
+        // Steep tangents overshoot.
+        let cubic = Interpolation::from_attribute(CUBIC, &[4.0, 4.0, 0.0, 0.0]);
+        let cubic = curve(&[0.0, 1.0], cubic);
+        assert!(cubic.sample(quarter) > 0.5);
+        assert!((cubic.sample(quarter * 2) - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn full_turn_and_step() {
+        let tree = ascii::parse(SPINNER).unwrap();
+        let doc = Loader::new().load_from_tree(tree).unwrap();
+        let spinner = doc
+            .objects()
+            .find(|obj| obj.name() == Some("Spinner"))
+            .unwrap()
+            .object_id();
+        let stack = doc.objects().find(is_animation_stack).unwrap();
+        let node = FbxObject {
+            name: Some("Spinner".to_owned()),
+            ..Default::default()
+        };
+        let hierarchy = HashMap::from_iter([(spinner, node)]);
+        let root = Name::new("root");
+        let frame_rate = doc.global_settings().unwrap().frame_rate().unwrap();
+        let mut diagnostics = Vec::new();
+        let clip = load_animation_stack(
+            &doc,
+            stack,
+            &root,
+            &[spinner],
+            &hierarchy,
+            1.0,
+            frame_rate,
+            &mut diagnostics,
+        )
+        .unwrap();
+
+        let path = EntityPath {
+            parts: vec![root, Name::new("Spinner")],
+        };
+        let curves = clip.get_curves_by_path(&path).unwrap();
+        let rotation = curves.iter().find_map(|curve| match &curve.keyframes {
+            Keyframes::Rotation(rotations) => Some((&curve.keyframe_timestamps, rotations)),
+            _ => None,
+        });
+        let (timestamps, rotations) = rotation.unwrap();
+        assert!(timestamps.len() > 30);
+        let angles = rotations
+            .windows(2)
+            .map(|pair| pair[0].angle_between(pair[1]));
+        assert!(angles.clone().all(|angle| angle < PI / 2.0));
+        assert!((angles.sum::<f32>() - 2.0 * PI).abs() < 1e-3);
+        let half = timestamps.iter().position(|t| *t == 0.5).unwrap();
+        let (axis, angle) = rotations[half].to_axis_angle();
+        assert!((angle - PI).abs() < 1e-3);
+        assert!(axis.abs_diff_eq(Vec3::Z, 1e-3));
+
+        let translation = curves.iter().find_map(|curve| match &curve.keyframes {
+            Keyframes::Translation(translations) => {
+                Some((&curve.keyframe_timestamps, translations))
+            }
+            _ => None,
+        });
+        let (timestamps, translations) = translation.unwrap();
+        let step = timestamps.iter().position(|t| *t == 0.5).unwrap();
+        assert!((timestamps[step] - timestamps[step - 1]) <= 0.001);
+        assert_eq!(translations[step - 1].x, 0.0);
+        assert_eq!(translations[step].x, 1.0);
+    }
+}
@@ -0,0 +1,683 @@
+//! Parse ASCII FBX files into the node tree of binary FBX files.
+//!
+//! The ASCII encoding doesn't say the type of attribute values, so we pick
+//! the type the binary encoding uses for the nodes the loader reads, based
+//! on the node names. Other integers are `i32`s (or `i64`s if they don't fit)
+//! and other decimal numbers are `f64`s.
+//
+// a bit of trivia on the ASCII encoding:
+// - Each node is a `Name: value, value, ...` line, optionally followed
+//   by its children between `{` and `}`. Long lists of values are wrapped
+//   after a comma, FBX 6 `Key`s start their values on the next line.
+//   `;` starts a comment.
+// - FBX 7 writes arrays as `Name: *length { a: value, value, ... }`,
+//   FBX 6 writes them as a plain list of values.
+// - Strings are quoted, `"` is escaped as `&quot;`. Object names are
+//   written `Class::Name` instead of `Name\0\x01Class`.
+// - Booleans are written as bare `Y`/`T` or `N`/`F` characters, other bare
+//   words (such as the `L` or `C` interpolation of FBX 6 keys) are strings.
+// - Binary data (embedded textures) is written as base64 strings.
+use std::{borrow::Cow, slice, str::FromStr};
+
+use anyhow::{bail, Context};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use fbxcel_dom::fbxcel::{
+    low::v7400::AttributeValue,
+    tree::v7400::{NodeId, Tree},
+};
+
+use crate::v6100;
+
+/// The first bytes of binary FBX files.
+const BINARY_MAGIC: &[u8] = b"Kaydara FBX Binary";
+
+/// Nodes holding an array of `f64`.
+const F64_ARRAYS: &[&str] = &[
+    "Vertices",
+    "Normals",
+    "NormalsW",
+    "Binormals",
+    "BinormalsW",
+    "Tangents",
+    "TangentsW",
+    "UV",
+    "Colors",
+    "Weights",
+    "Transform",
+    "TransformLink",
+    "TransformAssociateModel",
+    "Matrix",
+    "FullWeights",
+];
+/// Nodes holding an array of `i32`.
+const I32_ARRAYS: &[&str] = &[
+    "PolygonVertexIndex",
+    "Edges",
+    "NormalsIndex",
+    "BinormalsIndex",
+    "TangentsIndex",
+    "UVIndex",
+    "ColorIndex",
+    "Materials",
+    "TextureId",
+    "Smoothing",
+    "Indexes",
+    "KeyAttrFlags",
+    "KeyAttrRefCount",
+];
+/// Nodes holding an array of `f32`.
+const F32_ARRAYS: &[&str] = &["KeyValueFloat", "KeyAttrDataFloat"];
+/// Nodes holding an array of `i64`.
+const I64_ARRAYS: &[&str] = &["KeyTime"];
+
+/// Property types (`P` and `Property` nodes) with integer values.
+const I32_PROPERTIES: &[&str] = &[
+    "int",
+    "Integer",
+    "enum",
+    "Enum",
+    "bool",
+    "Bool",
+    "Visibility Inheritance",
+];
+/// Property types with 64 bits integer values.
+const I64_PROPERTIES: &[&str] = &["KTime", "ULongLong", "LongLong"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Type {
+    I32,
+    I64,
+    F32,
+    F64,
+}
+
+/// Whether `bytes` is an ASCII FBX file, rather than a binary one.
+pub(crate) fn is_ascii(bytes: &[u8]) -> bool {
+    !bytes.starts_with(BINARY_MAGIC)
+}
+
+/// Parse an ASCII FBX file.
+pub(crate) fn parse(bytes: &[u8]) -> anyhow::Result<Tree> {
+    let source = String::from_utf8_lossy(bytes);
+    let mut parser = Parser {
+        lexer: Lexer {
+            source: &source,
+            pos: 0,
+            line: 1,
+        },
+        peeked: None,
+    };
+    let nodes = parser.parse_nodes(false)?;
+
+    let mut tree = Tree::default();
+    let root = tree.root().node_id();
+    for node in &nodes {
+        append(&mut tree, root, "", node)?;
+    }
+    Ok(tree)
+}
+
+/// The `FBXVersion` of the `FBXHeaderExtension` of a parsed ASCII file,
+/// for example `7400` for FBX 7.4.
+pub(crate) fn version(tree: &Tree) -> Option<u32> {
+    let version = tree
+        .root()
+        .first_child_by_name("FBXHeaderExtension")?
+        .first_child_by_name("FBXVersion")?;
+    let version = version.attributes().first()?.get_i32()?;
+    u32::try_from(version).ok()
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Token<'a> {
+    /// A node name, followed by `:`.
+    Name(&'a str),
+    /// An unquoted value: a number, a boolean, or the `*length` of an array.
+    Word(&'a str),
+    /// A quoted string, without the quotes.
+    String(&'a str),
+    Comma,
+    Open,
+    Close,
+    Newline,
+}
+
+struct Lexer<'a> {
+    source: &'a str,
+    pos: usize,
+    line: usize,
+}
+impl<'a> Lexer<'a> {
+    fn next_token(&mut self) -> anyhow::Result<Option<Token<'a>>> {
+        let bytes = self.source.as_bytes();
+        loop {
+            let Some(&byte) = bytes.get(self.pos) else {
+                return Ok(None);
+            };
+            let token = match byte {
+                b'\n' => {
+                    self.pos += 1;
+                    self.line += 1;
+                    // Some exporters wrap lists before the comma rather than after.
+                    let rest = &bytes[self.pos..];
+                    let next = rest.iter().find(|byte| !byte.is_ascii_whitespace());
+                    if next == Some(&b',') {
+                        continue;
+                    }
+                    Token::Newline
+                }
+                b';' => {
+                    let rest = &bytes[self.pos..];
+                    self.pos += rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len());
+                    continue;
+                }
+                byte if byte.is_ascii_whitespace() => {
+                    self.pos += 1;
+                    continue;
+                }
+                b',' => {
+                    self.pos += 1;
+                    Token::Comma
+                }
+                b'{' => {
+                    self.pos += 1;
+                    Token::Open
+                }
+                b'}' => {
+                    self.pos += 1;
+                    Token::Close
+                }
+                b'"' => {
+                    let start = self.pos + 1;
+                    let Some(len) = bytes[start..].iter().position(|&b| b == b'"') else {
+                        bail!("Unterminated string at line {}", self.line);
+                    };
+                    let string = &self.source[start..start + len];
+                    self.line += string.matches('\n').count();
+                    self.pos = start + len + 1;
+                    Token::String(string)
+                }
+                _ => {
+                    let start = self.pos;
+                    let len = bytes[start..]
+                        .iter()
+                        .position(|&b| b.is_ascii_whitespace() || b",:{}\";".contains(&b))
+                        .unwrap_or(bytes.len() - start);
+                    let word = &self.source[start..start + len];
+                    self.pos += len;
+                    if word.is_empty() {
+                        bail!("Unexpected `:` at line {}", self.line);
+                    } else if bytes.get(self.pos) == Some(&b':') {
+                        self.pos += 1;
+                        Token::Name(word)
+                    } else {
+                        Token::Word(word)
+                    }
+                }
+            };
+            return Ok(Some(token));
+        }
+    }
+}
+
+enum Value<'a> {
+    Word(&'a str),
+    String(&'a str),
+    Array(Vec<&'a str>),
+}
+
+struct Node<'a> {
+    name: &'a str,
+    values: Vec<Value<'a>>,
+    children: Vec<Node<'a>>,
+}
+
+struct Parser<'a> {
+    lexer: Lexer<'a>,
+    peeked: Option<Option<Token<'a>>>,
+}
+impl<'a> Parser<'a> {
+    fn peek(&mut self) -> anyhow::Result<Option<Token<'a>>> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.lexer.next_token()?);
+        }
+        Ok(self.peeked.unwrap())
+    }
+    fn next(&mut self) -> anyhow::Result<Option<Token<'a>>> {
+        match self.peeked.take() {
+            Some(token) => Ok(token),
+            None => self.lexer.next_token(),
+        }
+    }
+
+    /// Parse nodes until the end of the file, or until `}` if `in_block`.
+    fn parse_nodes(&mut self, in_block: bool) -> anyhow::Result<Vec<Node<'a>>> {
+        let mut nodes = Vec::new();
+        loop {
+            match self.next()? {
+                None if in_block => bail!("Unexpected end of file, missing `}}`"),
+                None => return Ok(nodes),
+                Some(Token::Close) if in_block => return Ok(nodes),
+                Some(Token::Newline) => {}
+                Some(Token::Name(name)) => nodes.push(self.parse_node(name)?),
+                Some(token) => bail!(
+                    "Unexpected {token:?} at line {}, expected a node name",
+                    self.lexer.line
+                ),
+            }
+        }
+    }
+
+    fn parse_node(&mut self, name: &'a str) -> anyhow::Result<Node<'a>> {
+        let mut node = Node {
+            name,
+            values: Vec::new(),
+            children: Vec::new(),
+        };
+        loop {
+            match self.peek()? {
+                None | Some(Token::Close) => return Ok(node),
+                Some(Token::Newline) => {
+                    while let Some(Token::Newline) = self.peek()? {
+                        self.next()?;
+                    }
+                    // Values may start on the line after the name (`Key:` in FBX 6),
+                    // a line starting with a value continues the values of the node.
+                    if !matches!(self.peek()?, Some(Token::Word(_) | Token::String(_))) {
+                        return Ok(node);
+                    }
+                }
+                Some(Token::Open) => {
+                    self.next()?;
+                    node.children = self.parse_nodes(true)?;
+                    return Ok(node);
+                }
+                Some(Token::Comma) => {
+                    self.next()?;
+                    while let Some(Token::Newline) = self.peek()? {
+                        self.next()?;
+                    }
+                }
+                Some(Token::String(string)) => {
+                    self.next()?;
+                    node.values.push(Value::String(string));
+                }
+                Some(Token::Word(word)) if word.starts_with('*') => {
+                    self.next()?;
+                    let Some(Token::Open) = self.next()? else {
+                        bail!("Expected `{{` after `{word}` at line {}", self.lexer.line);
+                    };
+                    let array = self.parse_nodes(true)?.into_iter().find(|n| n.name == "a");
+                    let words = array.into_iter().flat_map(|array| array.values);
+                    let words = words.filter_map(|value| match value {
+                        Value::Word(word) => Some(word),
+                        _ => None,
+                    });
+                    node.values.push(Value::Array(words.collect()));
+                }
+                Some(Token::Word(word)) => {
+                    self.next()?;
+                    node.values.push(Value::Word(word));
+                }
+                Some(Token::Name(other)) => bail!(
+                    "Unexpected node {other} at line {}, in the values of {name}",
+                    self.lexer.line
+                ),
+            }
+        }
+    }
+}
+
+fn append(tree: &mut Tree, parent: NodeId, parent_name: &str, node: &Node) -> anyhow::Result<()> {
+    let id = tree.append_new(parent, node.name);
+    let attributes =
+        attributes(parent_name, node).with_context(|| format!("Invalid {} node", node.name))?;
+    tree.set_attributes_vec(id, attributes);
+    for child in &node.children {
+        append(tree, id, node.name, child)?;
+    }
+    Ok(())
+}
+
+fn attributes(parent_name: &str, node: &Node) -> anyhow::Result<Vec<AttributeValue>> {
+    if let Some(ty) = array_type(node.name) {
+        let words = node.values.iter().flat_map(|value| match value {
+            Value::Word(word) => slice::from_ref(word),
+            Value::Array(words) => words.as_slice(),
+            Value::String(_) => &[],
+        });
+        if node.name == "KeyAttrDataFloat" {
+            return Ok(vec![key_attr_data(words)?]);
+        }
+        return Ok(vec![array(ty, words)?]);
+    }
+    if node.name == "Content" {
+        let content: String = node.values.iter().filter_map(string_value).collect();
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+        let content = STANDARD.decode(content).context("Invalid base64 content")?;
+        return Ok(vec![AttributeValue::Binary(content)]);
+    }
+    // FBX 7 objects are `Class: id, "Class::Name", "Subclass"`
+    let is_object = parent_name == "Objects" && matches!(node.values.first(), Some(Value::Word(_)));
+
+    let attribute = |(index, value): (usize, &Value)| match value {
+        Value::String(string) if is_object && index == 1 => {
+            Ok(AttributeValue::String(v6100::name_class(&unescape(string))))
+        }
+        Value::String(string) => Ok(AttributeValue::String(unescape(string).into_owned())),
+        Value::Word(word) => Ok(scalar(word, scalar_type(parent_name, node, index))),
+        Value::Array(words) if words.iter().all(|word| word.parse::<i32>().is_ok()) => {
+            array(Type::I32, words.iter())
+        }
+        Value::Array(words) => array(Type::F64, words.iter()),
+    };
+    node.values.iter().enumerate().map(attribute).collect()
+}
+
+fn array_type(node_name: &str) -> Option<Type> {
+    let arrays = [
+        (Type::F64, F64_ARRAYS),
+        (Type::I32, I32_ARRAYS),
+        (Type::F32, F32_ARRAYS),
+        (Type::I64, I64_ARRAYS),
+    ];
+    let (ty, _) = arrays
+        .iter()
+        .find(|(_, names)| names.contains(&node_name))?;
+    Some(*ty)
+}
+
+/// The type of the integer value at `index` in `node`, if it's not the default `i32`.
+fn scalar_type(parent_name: &str, node: &Node, index: usize) -> Option<Type> {
+    match node.name {
+        "P" | "Property" => {
+            // `P: name, type, label, flags, values...`, `Property: name, type, flags, values...`
+            let first_value = if node.name == "P" { 4 } else { 3 };
+            let Some(Value::String(ty)) = node.values.get(1) else {
+                return None;
+            };
+            if index < first_value {
+                None
+            } else if I32_PROPERTIES.contains(ty) {
+                Some(Type::I32)
+            } else if I64_PROPERTIES.contains(ty) {
+                Some(Type::I64)
+            } else {
+                Some(Type::F64)
+            }
+        }
+        // Object ids
+        "C" | "Connect" | "RootNode" => Some(Type::I64),
+        "Document" if index == 0 => Some(Type::I64),
+        _ if parent_name == "Objects" && index == 0 => Some(Type::I64),
+        "DeformPercent" => Some(Type::F64),
+        _ => None,
+    }
+}
+
+/// The value of an unquoted `word`, other bare words than numbers and
+/// booleans (such as the interpolation of FBX 6 `Key`s) are strings.
+fn scalar(word: &str, ty: Option<Type>) -> AttributeValue {
+    let float = || word.parse().map(AttributeValue::F64).ok();
+    let value = match (word, ty) {
+        ("Y" | "T", _) => Some(AttributeValue::Bool(true)),
+        ("N" | "F", _) => Some(AttributeValue::Bool(false)),
+        (_, Some(Type::F64 | Type::F32)) => float(),
+        (_, Some(Type::I64)) => word.parse().map(AttributeValue::I64).ok().or_else(float),
+        _ => match (word.parse(), word.parse()) {
+            (Ok(value), _) => Some(AttributeValue::I32(value)),
+            (_, Ok(value)) => Some(AttributeValue::I64(value)),
+            _ => float(),
+        },
+    };
+    value.unwrap_or_else(|| AttributeValue::String(word.to_owned()))
+}
+
+fn array<'a>(ty: Type, words: impl Iterator<Item = &'a &'a str>) -> anyhow::Result<AttributeValue> {
+    let value = match ty {
+        Type::I32 => AttributeValue::ArrI32(words.map(|w| number(w)).collect::<Result<_, _>>()?),
+        Type::I64 => AttributeValue::ArrI64(words.map(|w| number(w)).collect::<Result<_, _>>()?),
+        Type::F32 => AttributeValue::ArrF32(words.map(|w| number(w)).collect::<Result<_, _>>()?),
+        Type::F64 => AttributeValue::ArrF64(words.map(|w| number(w)).collect::<Result<_, _>>()?),
+    };
+    Ok(value)
+}
+
+/// `KeyAttrDataFloat` are groups of four floats, the last two being packed
+/// integers (tangent weights and velocities), which ASCII files write as integers.
+fn key_attr_data<'a>(words: impl Iterator<Item = &'a &'a str>) -> anyhow::Result<AttributeValue> {
+    let value = |(index, word): (usize, &&str)| match word.parse::<i64>() {
+        Ok(bits) if index % 4 >= 2 => Ok(f32::from_bits(bits as u32)),
+        _ => number(word),
+    };
+    let data = words.enumerate().map(value).collect::<Result<_, _>>()?;
+    Ok(AttributeValue::ArrF32(data))
+}
+
+fn number<T: FromStr>(word: &str) -> anyhow::Result<T> {
+    word.parse()
+        .ok()
+        .with_context(|| format!("Invalid number {word}"))
+}
+
+fn string_value<'a>(value: &Value<'a>) -> Option<&'a str> {
+    match value {
+        Value::String(string) => Some(string),
+        _ => None,
+    }
+}
+
+fn unescape(string: &str) -> Cow<'_, str> {
+    if string.contains('&') {
+        let string = string.replace("&quot;", "\"").replace("&lf;", "\n");
+        Cow::Owned(string.replace("&cr;", "\r"))
+    } else {
+        Cow::Borrowed(string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fbxcel_dom::fbxcel::tree::v7400::NodeHandle;
+
+    use super::*;
+
+    fn child<'a>(node: NodeHandle<'a>, path: &[&str]) -> NodeHandle<'a> {
+        path.iter().fold(node, |node, name| {
+            node.first_child_by_name(name)
+                .unwrap_or_else(|| panic!("No {name} node"))
+        })
+    }
+
+    fn attributes<'a>(tree: &'a Tree, path: &[&str]) -> &'a [AttributeValue] {
+        child(tree.root(), path).attributes()
+    }
+
+    #[test]
+    fn arrays() {
+        let tree = parse(
+            br#"Geometry: 1, "Geometry::Cube", "Mesh" {
+                Vertices: *6 {
+                    a: 0,1,2,
+                    3.5,4,5
+                }
+                PolygonVertexIndex: *3 {
+                    a: 0,1,-2
+                }
+                Other: *2 {
+                    a: 1,2
+                }
+            }"#,
+        )
+        .unwrap();
+        let vertices = attributes(&tree, &["Geometry", "Vertices"]);
+        let expected = [0.0, 1.0, 2.0, 3.5, 4.0, 5.0].to_vec();
+        assert_eq!(vertices, [AttributeValue::ArrF64(expected)]);
+        let indices = attributes(&tree, &["Geometry", "PolygonVertexIndex"]);
+        assert_eq!(indices, [AttributeValue::ArrI32(vec![0, 1, -2])]);
+        let other = attributes(&tree, &["Geometry", "Other"]);
+        assert_eq!(other, [AttributeValue::ArrI32(vec![1, 2])]);
+    }
+
+    #[test]
+    fn key_attr_data() {
+        let tree = parse(
+            br#"AnimationCurve: 1, "AnimCurve::", "" {
+                KeyAttrDataFloat: *8 {
+                    a: 0,0,218434821,0,1.5,-2,-1,1
+                }
+            }"#,
+        )
+        .unwrap();
+        let data = attributes(&tree, &["AnimationCurve", "KeyAttrDataFloat"]);
+        let [AttributeValue::ArrF32(data)] = data else {
+            panic!("KeyAttrDataFloat is not a f32 array: {data:?}");
+        };
+        let bits: Vec<_> = data.iter().map(|float| float.to_bits()).collect();
+        assert_eq!(bits[2], 3333 << 16 | 3333);
+        assert_eq!(data[4..6], [1.5, -2.0]);
+        assert_eq!(bits[6..], [u32::MAX, 1]);
+    }
+
+    #[test]
+    fn multi_line_values() {
+        let tree = parse(
+            b"Model: \"Model::Cube\", \"Mesh\" {
+                Vertices: 0,1,2,
+                    3,4,5
+                PolygonVertexIndex: 0,1
+                    ,-3
+                Key: 
+                    1924423250,0,L,
+                    3848846500,1,C,n
+                Default: 0
+            }",
+        )
+        .unwrap();
+        let vertices = attributes(&tree, &["Model", "Vertices"]);
+        let expected = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0].to_vec();
+        assert_eq!(vertices, [AttributeValue::ArrF64(expected)]);
+        let indices = attributes(&tree, &["Model", "PolygonVertexIndex"]);
+        assert_eq!(indices, [AttributeValue::ArrI32(vec![0, 1, -3])]);
+        let key = attributes(&tree, &["Model", "Key"]);
+        assert_eq!(key.len(), 7);
+        assert_eq!(key[0], AttributeValue::I32(1924423250));
+        assert_eq!(key[3], AttributeValue::I64(3848846500));
+        assert_eq!(key[6], AttributeValue::String("n".to_owned()));
+        let default = attributes(&tree, &["Model", "Default"]);
+        assert_eq!(default, [AttributeValue::I32(0)]);
+    }
+
+    #[test]
+    fn comments() {
+        let tree = parse(
+            b"; FBX 7.4.0 project file
+            ; ----------------------------------------------------
+            FBXHeaderExtension:  {
+                ; the version of the file
+                FBXVersion: 7400 ; 7.4
+            }",
+        )
+        .unwrap();
+        assert_eq!(version(&tree), Some(7400));
+        let header = child(tree.root(), &["FBXHeaderExtension"]);
+        assert_eq!(header.children().count(), 1);
+    }
+
+    #[test]
+    fn content() {
+        let tree = parse(
+            b"Video: \"Video::Tex\", \"Clip\" {
+                Content: ,
+                    \"aGVsbG8g\",
+                    \"d29ybGQ=\"
+                RelativeFilename: \"tex.png\"
+            }
+            Empty: \"Video::Empty\", \"Clip\" {
+                Content: 
+            }",
+        )
+        .unwrap();
+        let content = attributes(&tree, &["Video", "Content"]);
+        assert_eq!(content, [AttributeValue::Binary(b"hello world".to_vec())]);
+        let file_name = attributes(&tree, &["Video", "RelativeFilename"]);
+        assert_eq!(file_name, [AttributeValue::String("tex.png".to_owned())]);
+        assert!(attributes(&tree, &["Empty", "Content"]).is_empty());
+    }
+
+    #[test]
+    fn bare_words() {
+        let tree = parse(
+            b"Node: Y, N, T, F, L, 1.5, 2
+            Property: \"Visibility\", \"Visibility\", \"A+\", 1
+            P: \"Lcl Translation\", \"Lcl Translation\", \"\", \"A\", 1, 2, 3",
+        )
+        .unwrap();
+        let node = attributes(&tree, &["Node"]);
+        let expected = [
+            AttributeValue::Bool(true),
+            AttributeValue::Bool(false),
+            AttributeValue::Bool(true),
+            AttributeValue::Bool(false),
+            AttributeValue::String("L".to_owned()),
+            AttributeValue::F64(1.5),
+            AttributeValue::I32(2),
+        ];
+        assert_eq!(node, expected);
+        let property = attributes(&tree, &["Property"]);
+        assert_eq!(property[3], AttributeValue::F64(1.0));
+        let p = attributes(&tree, &["P"]);
+        assert_eq!(p[4..], [1.0, 2.0, 3.0].map(AttributeValue::F64));
+    }
+
+    #[test]
+    fn fbx6_takes() {
+        let tree = parse(
+            b"Objects:  {
+            }
+            Takes:  {
+                Current: \"Take 001\"
+                Take: \"Take 001\" {
+                    Model: \"Model::Cube\" {
+                        Channel: \"Transform\" {
+                            Channel: \"T\" {
+                                Channel: \"X\" {
+                                    Default: 0
+                                    KeyVer: 4005
+                                    KeyCount: 2
+                                    Key: 
+                                        1924423250,0,L,
+                                        3848846500,1,L
+                                    Color: 1,0,0
+                                }
+                            }
+                        }
+                    }
+                }
+            }",
+        )
+        .unwrap();
+        let channel = ["Takes", "Take", "Model", "Channel", "Channel", "Channel"];
+        let channel = child(tree.root(), &channel);
+        let key = channel.first_child_by_name("Key").unwrap().attributes();
+        assert_eq!(key.len(), 6);
+        assert_eq!(key[2], AttributeValue::String("L".to_owned()));
+        assert!(channel.first_child_by_name("Color").is_some());
+
+        let mut diagnostics = Vec::new();
+        v6100::load_tree(&tree, &mut diagnostics).unwrap();
+        let warning = diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic.message.contains("Animations of FBX 6"));
+        assert!(warning.is_some(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn errors() {
+        assert!(parse(b"Node: {").is_err());
+        assert!(parse(b"Node: \"unterminated").is_err());
+        assert!(parse(b"Vertices: 1, nan_value").is_err());
+    }
+}
@@ -0,0 +1,128 @@
+//! Convert FBX `Camera` node attributes into bevy camera projections.
+
+// a bit of trivia on how cameras are encoded in FBX:
+// - A camera is a Model node (subclass "Camera") with a NodeAttribute
+//   (also subclass "Camera") holding the camera properties.
+// - The camera looks along the +X axis of its node, with +Y up.
+//   Bevy cameras look along -Z, so we need to add a rotation.
+// - The field of view is described by `ApertureMode`, it tells which of
+//   `FieldOfView`, `FieldOfViewX`, `FieldOfViewY` or `FocalLength`
+//   should be used to compute the projection.
+// - `FilmWidth` and `FilmHeight` are in inches, `FocalLength` is in millimeters.
+//
+// See http://docs.autodesk.com/FBX/2014/ENU/FBX-SDK-Documentation/cpp_ref/class_fbx_camera.html
+use std::f32::consts::FRAC_PI_2;
+
+use anyhow::{bail, Context, Result};
+use bevy::{
+    prelude::{OrthographicProjection, PerspectiveProjection, Projection, Quat, Transform},
+    render::camera::ScalingMode,
+};
+use fbxcel_dom::v7400::object::{
+    model::ModelHandle, nodeattribute::TypedNodeAttributeHandle, property::ObjectProperties,
+};
+
+use crate::utils::fbx_extend::{Loadable, ModelHandleExt};
+
+const MILLIMETERS_PER_INCH: f32 = 25.4;
+
+/// The `ApertureMode` property of FBX cameras.
+#[derive(Clone, Copy, Debug)]
+enum ApertureMode {
+    /// Use `FieldOfViewX` and `FieldOfViewY`.
+    HorizontalAndVertical,
+    /// `FieldOfView` is the horizontal field of view.
+    Horizontal,
+    /// `FieldOfView` is the vertical field of view.
+    Vertical,
+    /// Use `FocalLength` and the film size.
+    FocalLength,
+}
+impl From<i32> for ApertureMode {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => ApertureMode::HorizontalAndVertical,
+            1 => ApertureMode::Horizontal,
+            3 => ApertureMode::FocalLength,
+            _ => ApertureMode::Vertical,
+        }
+    }
+}
+
+/// The rotation to apply to a bevy camera so that it looks in the
+/// same direction as the FBX camera node it is a child of.
+pub(crate) fn camera_correction() -> Transform {
+    Transform::from_rotation(Quat::from_rotation_y(-FRAC_PI_2))
+}
+
+/// Compute the bevy [`Projection`] of a FBX camera node.
+///
+/// Distances are in FBX units multiplied by `unit_scale`. When the unit
+/// conversion isn't baked, this works because the camera entity
+/// inherits the scale of the scene root, like the rest of the scene.
+pub(crate) fn load_camera(model: ModelHandle, unit_scale: f32) -> Result<Projection> {
+    let Some(TypedNodeAttributeHandle::Camera(attribute)) = model.node_attribute() else {
+        bail!("Camera node without camera attribute");
+    };
+    let p = attribute.properties_by_native_typename("FbxCamera");
+    fn load<T: Loadable>(p: ObjectProperties, attribute: &str, default: T) -> T {
+        T::get_property(p, attribute).unwrap_or(default)
+    }
+    let near = load(p, "NearPlane", 10.0_f64) as f32 * unit_scale;
+    let far = load(p, "FarPlane", 4000.0_f64) as f32 * unit_scale;
+    let film_width = load(p, "FilmWidth", 0.816_f64) as f32;
+    let film_height = load(p, "FilmHeight", 0.612_f64) as f32;
+    let aspect_width = load(p, "AspectWidth", 0.0_f64) as f32;
+    let aspect_height = load(p, "AspectHeight", 0.0_f64) as f32;
+    let film_aspect = if film_height > 0.0 {
+        film_width / film_height
+    } else {
+        1.0
+    };
+    let aspect_ratio = if aspect_width > 0.0 && aspect_height > 0.0 {
+        aspect_width / aspect_height
+    } else {
+        film_aspect
+    };
+
+    let projection_type = load(p, "CameraProjectionType", 0_i32);
+    if projection_type == 1 {
+        let zoom = load(p, "OrthoZoom", 1.0_f64) as f32 * unit_scale;
+        return Ok(Projection::Orthographic(OrthographicProjection {
+            near,
+            far,
+            scaling_mode: ScalingMode::AutoMax {
+                max_width: zoom,
+                max_height: zoom,
+            },
+            ..Default::default()
+        }));
+    }
+    let horizontal_to_vertical = |fov: f32| 2.0 * ((fov / 2.0).tan() / film_aspect).atan();
+    let fov = load(p, "FieldOfView", 25.115_f64).to_radians() as f32;
+    let fov = match ApertureMode::from(load(p, "ApertureMode", 2_i32)) {
+        ApertureMode::HorizontalAndVertical => {
+            load(p, "FieldOfViewY", 40.0_f64).to_radians() as f32
+        }
+        ApertureMode::Horizontal => horizontal_to_vertical(fov),
+        ApertureMode::Vertical => fov,
+        ApertureMode::FocalLength => {
+            let focal_length = load(p, "FocalLength", 34.89_f64) as f32;
+            let film_height = film_height * MILLIMETERS_PER_INCH;
+            2.0 * (film_height / (2.0 * focal_length)).atan()
+        }
+    };
+    (fov > 0.0 && near > 0.0)
+        .then_some(Projection::Perspective(PerspectiveProjection {
+            fov,
+            aspect_ratio,
+            near,
+            far,
+        }))
+        .context("Invalid camera field of view or near plane")
+}
+
+/// Whether `model` is a camera node.
+pub(crate) fn is_camera(model: &ModelHandle) -> bool {
+    model.subclass() == "Camera"
+}
@@ -1,6 +1,13 @@
+#[cfg(feature = "animation")]
+use bevy::animation::AnimationClip;
 use bevy::{
-    prelude::{Handle, Image, Mesh, StandardMaterial, Transform},
+    math::{Affine2, Vec2},
+    prelude::{
+        debug, error, warn, DirectionalLight, Handle, Image, Mesh, Name, PointLight, Projection,
+        SpotLight, StandardMaterial, Transform,
+    },
     reflect::TypeUuid,
+    render::mesh::skinning::SkinnedMeshInverseBindposes,
     utils::HashMap,
 };
 use fbxcel_dom::v7400::object::ObjectId;
@@ -11,6 +18,79 @@ pub struct FbxMesh {
     pub name: Option<String>,
     pub bevy_mesh_handles: Vec<Handle<Mesh>>,
     pub materials: Vec<Handle<StandardMaterial>>,
+    /// The skeleton deforming this mesh, if any.
+    pub skin: Option<FbxSkin>,
+    /// The blend shapes of this mesh, one per `BlendShapeChannel`.
+    pub morph_targets: Vec<FbxMorphTarget>,
+    /// The geometric transform of the mesh node, relative to the node itself.
+    ///
+    /// It is the `Transform` of the entities spawned with the mesh primitives,
+    /// contrary to the node transform, it doesn't affect the node's children.
+    pub transform: Transform,
+    /// Names of the UV sets of the mesh.
+    ///
+    /// In order, they are stored in the `Mesh::ATTRIBUTE_UV_0`, [`ATTRIBUTE_UV_1`],
+    /// [`ATTRIBUTE_UV_2`] and [`ATTRIBUTE_UV_3`] vertex attributes.
+    /// Since `StandardMaterial` only uses `Mesh::ATTRIBUTE_UV_0`, if the textures
+    /// of a material are mapped to another UV set, the loader swaps it
+    /// with the first one in the mesh of this material.
+    /// The material's texture UV transform may also be baked into it,
+    /// see [`MaterialLoader::baked_uv_transform`].
+    ///
+    /// [`ATTRIBUTE_UV_1`]: crate::ATTRIBUTE_UV_1
+    /// [`ATTRIBUTE_UV_2`]: crate::ATTRIBUTE_UV_2
+    /// [`ATTRIBUTE_UV_3`]: crate::ATTRIBUTE_UV_3
+    /// [`MaterialLoader::baked_uv_transform`]: crate::material_loader::MaterialLoader::baked_uv_transform
+    pub uv_sets: Vec<String>,
+}
+
+/// A blend shape of a [`FbxMesh`], read from a `BlendShapeChannel` and its target `Shape`.
+///
+/// Bevy doesn't support morph targets yet, so the loader doesn't apply them,
+/// this only exposes the data.
+///
+/// The deltas are in the same space as `Mesh::ATTRIBUTE_POSITION` and `Mesh::ATTRIBUTE_NORMAL`.
+#[derive(Debug, Clone)]
+pub struct FbxMorphTarget {
+    /// Name of the `BlendShapeChannel`.
+    pub name: String,
+    /// The default influence of the target, from 0 to 1.
+    ///
+    /// This is the `DeformPercent` of the channel divided by 100.
+    pub default_weight: f32,
+    /// The deltas of each mesh in [`FbxMesh::bevy_mesh_handles`], in the same order.
+    pub primitives: Vec<FbxMorphDeltas>,
+}
+
+/// The per-vertex deltas of a [`FbxMorphTarget`] for a single bevy `Mesh`.
+///
+/// They are in the same order as the vertex attributes of the mesh.
+#[derive(Debug, Clone, Default)]
+pub struct FbxMorphDeltas {
+    /// Offset to add to each vertex position when the target is fully applied.
+    pub positions: Vec<[f32; 3]>,
+    /// Offset to add to each vertex normal when the target is fully applied.
+    ///
+    /// All zeros if the file doesn't define normals for this shape.
+    pub normals: Vec<[f32; 3]>,
+}
+
+/// Skinning data of a [`FbxMesh`], read from its `Skin` deformer.
+///
+/// The loader uses this to add a [`SkinnedMesh`] component
+/// to the entities of the spawned [`Scene`].
+///
+/// [`SkinnedMesh`]: bevy::render::mesh::skinning::SkinnedMesh
+/// [`Scene`]: bevy::scene::Scene
+#[derive(Debug, Clone)]
+pub struct FbxSkin {
+    /// The nodes used as joints, in the order of the `Mesh::ATTRIBUTE_JOINT_INDEX`
+    /// vertex attribute values.
+    ///
+    /// The last joint may be the mesh node itself, it is added
+    /// when some vertices are not fully weighted by the `Cluster`s of the skin.
+    pub joints: Vec<ObjectId>,
+    pub inverse_bindposes: Handle<SkinnedMeshInverseBindposes>,
 }
 
 /// The data loaded from a FBX scene.
@@ -30,10 +110,161 @@ pub struct FbxScene {
     pub name: Option<String>,
     pub bevy_meshes: HashMap<Handle<Mesh>, String>,
     pub materials: HashMap<String, Handle<StandardMaterial>>,
+    /// The images of the textures, by label.
+    ///
+    /// Textures using the same image file, or the same embedded image,
+    /// share a single entry, labelled after the first of them.
+    /// When several textures share a name, the others are labelled with their object id.
     pub textures: HashMap<String, Handle<Image>>,
     pub meshes: HashMap<ObjectId, Handle<FbxMesh>>,
     pub hierarchy: HashMap<ObjectId, FbxObject>,
     pub roots: Vec<ObjectId>,
+    /// The projection of each camera node.
+    ///
+    /// Only filled when [`FbxLoaderSettings::load_cameras`] is enabled.
+    ///
+    /// [`FbxLoaderSettings::load_cameras`]: crate::FbxLoaderSettings::load_cameras
+    pub cameras: HashMap<ObjectId, Projection>,
+    /// The light of each light node.
+    pub lights: HashMap<ObjectId, FbxLight>,
+    /// The `AnimationStack`s of the file, in the order they are declared.
+    ///
+    /// To play them, add an `AnimationPlayer` to the FBX scene root entity,
+    /// the one with the `Name` "Fbx scene root".
+    #[cfg(feature = "animation")]
+    pub animations: Vec<Handle<AnimationClip>>,
+    /// The `AnimationStack`s of the file, by name.
+    #[cfg(feature = "animation")]
+    pub named_animations: HashMap<String, Handle<AnimationClip>>,
+    /// Problems encountered while loading the file, in the order they were found.
+    ///
+    /// With [`FbxLoaderSettings::lenient`], this also lists the objects
+    /// that were skipped or replaced by a placeholder, as errors.
+    ///
+    /// [`FbxLoaderSettings::lenient`]: crate::FbxLoaderSettings::lenient
+    pub diagnostics: Vec<FbxDiagnostic>,
+}
+
+/// How bad a [`FbxDiagnostic`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FbxDiagnosticSeverity {
+    /// Something of the file was ignored, without visible consequences.
+    Info,
+    /// Something of the file couldn't be loaded faithfully.
+    Warning,
+    /// Something of the file couldn't be loaded at all.
+    Error,
+}
+
+/// A problem encountered while loading a FBX file, see [`FbxScene::diagnostics`].
+#[derive(Debug, Clone)]
+pub struct FbxDiagnostic {
+    pub severity: FbxDiagnosticSeverity,
+    /// The FBX object the problem is about, if any.
+    pub object: Option<ObjectId>,
+    /// The name of [`Self::object`], if it has one.
+    pub name: Option<String>,
+    pub message: String,
+}
+impl FbxDiagnostic {
+    pub(crate) fn new(severity: FbxDiagnosticSeverity, message: impl Into<String>) -> Self {
+        FbxDiagnostic {
+            severity,
+            object: None,
+            name: None,
+            message: message.into(),
+        }
+    }
+    pub(crate) fn info(message: impl Into<String>) -> Self {
+        Self::new(FbxDiagnosticSeverity::Info, message)
+    }
+    pub(crate) fn warning(message: impl Into<String>) -> Self {
+        Self::new(FbxDiagnosticSeverity::Warning, message)
+    }
+    pub(crate) fn error(message: impl Into<String>) -> Self {
+        Self::new(FbxDiagnosticSeverity::Error, message)
+    }
+    pub(crate) fn with_object(mut self, object: ObjectId, name: Option<&str>) -> Self {
+        self.object = Some(object);
+        self.name = name.filter(|name| !name.is_empty()).map(str::to_owned);
+        self
+    }
+    /// Log this diagnostic and add it to `diagnostics`.
+    pub(crate) fn report(self, diagnostics: &mut Vec<FbxDiagnostic>) {
+        let message = match &self.name {
+            Some(name) => format!("{name}: {}", self.message),
+            None => self.message.clone(),
+        };
+        match self.severity {
+            FbxDiagnosticSeverity::Info => debug!("{message}"),
+            FbxDiagnosticSeverity::Warning => warn!("{message}"),
+            FbxDiagnosticSeverity::Error => error!("{message}"),
+        }
+        diagnostics.push(self);
+    }
+}
+
+/// The UV transform of a FBX `Texture`, read from its `Translation`, `Rotation`,
+/// `Scaling`, `TextureRotationPivot`, `TextureScalingPivot` and `UVSwap` properties.
+///
+/// It is in FBX UV space, where V points up, see [`Self::to_affine2`].
+/// The default value is the identity transform.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FbxTextureTransform {
+    pub translation: Vec2,
+    /// Counterclockwise rotation in radians, around [`Self::rotation_pivot`].
+    pub rotation: f32,
+    /// Scale, around [`Self::scaling_pivot`].
+    pub scale: Vec2,
+    pub rotation_pivot: Vec2,
+    pub scaling_pivot: Vec2,
+    /// Whether U and V are swapped before applying the rest of the transform.
+    pub uv_swap: bool,
+}
+impl Default for FbxTextureTransform {
+    fn default() -> Self {
+        FbxTextureTransform {
+            translation: Vec2::ZERO,
+            rotation: 0.0,
+            scale: Vec2::ONE,
+            rotation_pivot: Vec2::ZERO,
+            scaling_pivot: Vec2::ZERO,
+            uv_swap: false,
+        }
+    }
+}
+impl FbxTextureTransform {
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+    /// The transform to apply to FBX UV coordinates to get the texture coordinates.
+    ///
+    /// Note that bevy's V axis points down, the FBX `(u, v)` coordinates
+    /// of bevy's `(u, v)` UVs are `(u, 1 - v)`.
+    pub fn to_affine2(&self) -> Affine2 {
+        let around = |pivot: Vec2, transform: Affine2| {
+            Affine2::from_translation(pivot) * transform * Affine2::from_translation(-pivot)
+        };
+        let swap = if self.uv_swap {
+            Affine2::from_cols(Vec2::Y, Vec2::X, Vec2::ZERO)
+        } else {
+            Affine2::IDENTITY
+        };
+        Affine2::from_translation(self.translation)
+            * around(self.rotation_pivot, Affine2::from_angle(self.rotation))
+            * around(self.scaling_pivot, Affine2::from_scale(self.scale))
+            * swap
+    }
+}
+
+/// A light read from a FBX `Light` node.
+///
+/// See the `light` module for how FBX light properties are converted.
+#[derive(Debug, Clone)]
+pub enum FbxLight {
+    Point(PointLight),
+    Spot(SpotLight),
+    Directional(DirectionalLight),
 }
 
 /// An FBX object in the scene tree.
@@ -52,3 +283,14 @@ pub struct FbxObject {
     /// `HashMap`s of the [`FbxScene`] structure.
     pub children: Vec<ObjectId>,
 }
+impl FbxObject {
+    /// The [`Name`] of the entity spawned for this object.
+    ///
+    /// Animations refer to entities by name, so even unnamed nodes get one.
+    pub(crate) fn entity_name(&self, id: ObjectId) -> Name {
+        match &self.name {
+            Some(name) => Name::new(name.clone()),
+            None => Name::new(format!("FbxNode{}", id.raw())),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! Errors of the [`FbxLoader`](crate::FbxLoader).
+
+use std::{error::Error, fmt};
+
+/// Why a FBX file failed to load.
+///
+/// This is the error of failed `FbxLoader` loads. Bevy's `AssetLoader` errors
+/// are `anyhow::Error`s, use `error.downcast_ref::<FbxError>()` to get it back.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum FbxError {
+    /// The file is not a valid FBX file.
+    Parse(anyhow::Error),
+    /// The file is a FBX file of a version this loader doesn't support.
+    UnsupportedVersion { major: u32, minor: u32 },
+    /// A texture image couldn't be read or decoded.
+    Texture { name: String, source: anyhow::Error },
+    /// A mesh has invalid geometry.
+    InvalidGeometry { mesh: String, source: anyhow::Error },
+    /// The file is a valid FBX file, but its content couldn't be loaded.
+    Other(anyhow::Error),
+}
+impl fmt::Display for FbxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FbxError::Parse(_) => write!(f, "Failed to parse FBX file"),
+            FbxError::UnsupportedVersion { major, minor } => {
+                write!(f, "Unsupported FBX version: {major}.{minor}")
+            }
+            FbxError::Texture { name, .. } => write!(f, "Failed to load texture {name}"),
+            FbxError::InvalidGeometry { mesh, .. } => write!(f, "Invalid geometry in {mesh}"),
+            FbxError::Other(_) => write!(f, "Failed to load FBX scene"),
+        }
+    }
+}
+impl Error for FbxError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FbxError::Parse(source)
+            | FbxError::Texture { source, .. }
+            | FbxError::InvalidGeometry { source, .. }
+            | FbxError::Other(source) => Some(source.as_ref()),
+            FbxError::UnsupportedVersion { .. } => None,
+        }
+    }
+}
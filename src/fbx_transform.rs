@@ -87,7 +87,7 @@ struct NodeRotation {
 /// where we set the local transform infered
 /// from the computed FBX global position.
 #[derive(Clone, Debug)]
-struct FbxNodeTransformInfo {
+pub(crate) struct FbxNodeTransformInfo {
     rotation: NodeRotation,
     translation: Translation,
     scale: NodeScale,
@@ -96,8 +96,8 @@ struct FbxNodeTransformInfo {
 impl FbxNodeTransformInfo {
     // if you were wondering: "Lcl" stands for "Local"
     // FIXME: Non-zero {Rotation,Scaling}{Pivot,Offset} is untested.
-    // TODO: Geometric{Translation,Scaling,Rotation}
-    // (see docs.autodesk.com and stackoverflow.com links at top of this file)
+    // NOTE: Geometric{Translation,Scaling,Rotation} are handled separately
+    // in `geometric_transform`, since they do not propagate to children.
     fn from_object(object: ObjectHandle) -> Result<Self> {
         fn load<T: Loadable>(p: ObjectProperties, attribute: &str) -> Result<T> {
             T::get_property(p, attribute)
@@ -121,10 +121,61 @@ impl FbxNodeTransformInfo {
             inherit_type: load(p, "InheritType")?,
         })
     }
+    #[cfg(feature = "animation")]
+    pub(crate) fn from_node(node: ModelHandle) -> Result<Self> {
+        Self::from_object(*node)
+    }
+    /// Replace the `Lcl` properties with the provided ones, keeping the other
+    /// transform components (such as pivots and pre/post rotations).
+    ///
+    /// This is used to compute the transform of animated nodes.
+    #[cfg(feature = "animation")]
+    pub(crate) fn with_lcl(&self, lcl: LclValues) -> Self {
+        let mut info = self.clone();
+        let euler = info.rotation.local.1;
+        info.translation = Translation::from_double(lcl.translation);
+        info.rotation.local = Rotation::from_euler(euler, lcl.rotation);
+        info.scale.local = Scale::from_double(lcl.scaling);
+        info
+    }
 }
 
+/// The `Lcl {Translation,Rotation,Scaling}` properties of a node.
+///
+/// Values are as stored in the FBX file, meaning rotations are euler angles in degrees.
+#[cfg(feature = "animation")]
 #[derive(Copy, Clone, Debug)]
-pub(crate) struct LocalScale(Scale);
+pub(crate) struct LclValues {
+    pub(crate) translation: DVec3,
+    pub(crate) rotation: DVec3,
+    pub(crate) scaling: DVec3,
+}
+#[cfg(feature = "animation")]
+impl LclValues {
+    pub(crate) fn from_node(node: ModelHandle) -> Result<Self> {
+        let p = node.properties_by_native_typename("FbxNode");
+        Ok(LclValues {
+            translation: DVec3::get_property(p, "Lcl Translation")?,
+            rotation: DVec3::get_property(p, "Lcl Rotation")?,
+            scaling: DVec3::get_property(p, "Lcl Scaling")?,
+        })
+    }
+}
+
+/// The `Geometric{Translation,Rotation,Scaling}` of a node.
+///
+/// This is an offset applied to the node's own geometry (what 3ds Max calls "object offset"),
+/// children of the node are not affected by it.
+///
+/// Contrary to the node transform, the geometric rotation is always in XYZ order.
+pub(crate) fn geometric_transform(node: ModelHandle) -> Result<Mat4> {
+    let p = node.properties_by_native_typename("FbxNode");
+    let translation = Translation::from_double(DVec3::get_property(p, "GeometricTranslation")?);
+    let rotation =
+        Rotation::from_euler(EulerRot::XYZ, DVec3::get_property(p, "GeometricRotation")?);
+    let scale = Scale::from_double(DVec3::get_property(p, "GeometricScaling")?);
+    Ok(translation.mat() * rotation.mat() * scale.mat())
+}
 
 // This is similar to mat.to_scale_rotation_translation()
 // but takes into account shear operations (meaning: rotation followed by non-uniform scale)
@@ -194,11 +245,18 @@ impl Default for FbxTransform {
     }
 }
 impl FbxTransform {
-    pub(crate) fn from_node(node: ModelHandle, parent: Option<FbxTransform>) -> Self {
-        let transform = FbxNodeTransformInfo::from_object(*node).unwrap();
-        FbxTransform::from_fbxtrans(transform, parent)
+    pub(crate) fn from_node(node: ModelHandle, parent: Option<FbxTransform>) -> Result<Self> {
+        let transform = FbxNodeTransformInfo::from_object(*node)?;
+        Ok(FbxTransform::from_fbxtrans(transform, parent))
+    }
+    /// The transform of a node with an identity local transform.
+    pub(crate) fn identity(parent: Option<FbxTransform>) -> Self {
+        FbxTransform {
+            local_scale: Scale::IDENTITY,
+            global: parent.map_or(Mat4::IDENTITY, |parent| parent.global),
+        }
     }
-    fn from_fbxtrans(trans: FbxNodeTransformInfo, parent: Option<FbxTransform>) -> Self {
+    pub(crate) fn from_fbxtrans(trans: FbxNodeTransformInfo, parent: Option<FbxTransform>) -> Self {
         FbxTransform {
             local_scale: trans.scale.local,
             global: global_transform(trans, parent),
@@ -1,15 +1,44 @@
-use bevy::prelude::{AddAsset, App, Plugin, Resource};
+use std::sync::Arc;
 
-pub use data::{FbxMesh, FbxScene};
+use bevy::{
+    asset::{AssetEvent, Assets},
+    prelude::{AddAsset, App, EventReader, EventWriter, Handle, Plugin, Res, Resource},
+    render::{mesh::MeshVertexAttribute, render_resource::VertexFormat},
+};
+
+pub use data::{
+    FbxDiagnostic, FbxDiagnosticSeverity, FbxLight, FbxMesh, FbxMorphDeltas, FbxMorphTarget,
+    FbxScene, FbxSkin, FbxTextureTransform,
+};
+pub use error::FbxError;
 pub use loader::FbxLoader;
 
+#[cfg(feature = "animation")]
+pub(crate) mod animation;
+pub(crate) mod ascii;
+pub(crate) mod camera;
 pub(crate) mod data;
+pub(crate) mod error;
 pub(crate) mod fbx_transform;
+pub(crate) mod light;
 pub(crate) mod loader;
 pub mod material_loader;
+pub mod texture_resolver;
 pub(crate) mod utils;
+pub(crate) mod v6100;
 
 use material_loader::MaterialLoader;
+use texture_resolver::{DefaultTextureResolver, TextureResolver};
+
+/// The second UV set of FBX meshes, see [`FbxMesh::uv_sets`].
+pub const ATTRIBUTE_UV_1: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_Uv_1", 1_284_091_067, VertexFormat::Float32x2);
+/// The third UV set of FBX meshes, see [`FbxMesh::uv_sets`].
+pub const ATTRIBUTE_UV_2: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_Uv_2", 1_284_091_068, VertexFormat::Float32x2);
+/// The fourth UV set of FBX meshes, see [`FbxMesh::uv_sets`].
+pub const ATTRIBUTE_UV_3: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_Uv_3", 1_284_091_069, VertexFormat::Float32x2);
 
 /// Adds support for FBX file loading to the app.
 #[derive(Default)]
@@ -34,10 +63,165 @@ impl Default for FbxMaterialLoaders {
     }
 }
 
+/// Resource to control how the `FbxLoader` finds the image files
+/// of textures that are not embedded in the FBX file.
+///
+/// See [`TextureResolver`] documentation for more details.
+///
+/// Like [`FbxMaterialLoaders`], insert this as a resource
+/// **before** adding the `FbxPlugin` to the app.
+///
+/// The default resolver is a [`DefaultTextureResolver`] without search directories.
+#[derive(Clone, Resource)]
+pub struct FbxTextureResolver(pub Arc<dyn TextureResolver>);
+impl Default for FbxTextureResolver {
+    fn default() -> Self {
+        Self(Arc::new(DefaultTextureResolver::default()))
+    }
+}
+
+/// Resource to control what the `FbxLoader` imports.
+///
+/// Like [`FbxMaterialLoaders`], insert this as a resource
+/// **before** adding the `FbxPlugin` to the app.
+#[derive(Clone, Debug, Resource)]
+pub struct FbxLoaderSettings {
+    /// Spawn the FBX `Camera` nodes as entities with a `Camera3dBundle`.
+    ///
+    /// The cameras are spawned with `Camera::is_active` set to `false`,
+    /// so that they do not conflict with the cameras of your app.
+    /// Set it to `true` on the camera you want to render the scene with.
+    ///
+    /// Disabled by default.
+    pub load_cameras: bool,
+    /// How close vertex attributes should be for vertices to be merged.
+    ///
+    /// FBX stores vertex attributes per polygon corner, the loader merges
+    /// corners with identical attributes into a single vertex. Attribute values are
+    /// snapped to a grid of `vertex_weld_tolerance` sized cells before
+    /// comparing them, the default `0.0` only merges exactly equal vertices.
+    pub vertex_weld_tolerance: f32,
+    /// Whether vertex colors are stored as linear colors in the FBX file.
+    ///
+    /// By default, vertex colors are considered sRGB (which is what most
+    /// modeling software display when painting vertex colors) and converted
+    /// to linear colors for `Mesh::ATTRIBUTE_COLOR`.
+    pub linear_vertex_colors: bool,
+    /// How to compute the normals of meshes that do not have any.
+    pub generated_normals: GeneratedNormals,
+    /// Do not convert the scene from the axis system of the FBX file
+    /// to bevy's Y-up right-handed axis system.
+    ///
+    /// By default, the scene root is rotated (or mirrored) according to the
+    /// `UpAxis`, `FrontAxis` and `CoordAxis` of the file, so that
+    /// for example Z-up scenes are not lying on their side.
+    pub ignore_axis_system: bool,
+    /// The size of a bevy unit, in meters.
+    ///
+    /// The unit of the FBX file (its `UnitScaleFactor`) is converted
+    /// to this unit. Defaults to `1.0`, bevy units being meters.
+    pub unit_in_meters: f32,
+    /// Apply the unit conversion to vertex positions, node translations,
+    /// skins and animations instead of scaling the scene root.
+    ///
+    /// By default, the unit conversion is a scale on the scene root
+    /// `Transform` (for example `0.01` for files in centimeters), inherited
+    /// by everything in the scene, including physics colliders.
+    pub bake_unit_scale: bool,
+    /// Load as much of the file as possible instead of failing
+    /// on the first broken object.
+    ///
+    /// Meshes, cameras, lights and animations that fail to load are skipped,
+    /// materials are replaced by the [`material_loader::LOAD_FALLBACK`] material,
+    /// textures by a magenta checker texture and broken node transforms by
+    /// the identity. Each failure is recorded in [`FbxScene::diagnostics`].
+    ///
+    /// Disabled by default.
+    pub lenient: bool,
+    /// Load the color textures (base color, emissive...) that are not
+    /// embedded in the FBX file with the `AssetServer`, rather than as
+    /// labeled assets of the FBX file.
+    ///
+    /// Images shared by several FBX files are then loaded only once, and
+    /// are hot reloaded when the `AssetServer` watches for changes.
+    /// However, they use the `ImagePlugin` default sampler, ignoring the
+    /// wrap modes of the FBX texture. Other textures, such as normal maps,
+    /// are still loaded by the `FbxLoader`, since the `AssetServer` loads
+    /// images in the sRGB color space. So are textures when the `AssetIo`
+    /// can't check which files exist, as on the web and Android.
+    ///
+    /// Disabled by default.
+    pub external_textures_as_assets: bool,
+}
+impl Default for FbxLoaderSettings {
+    fn default() -> Self {
+        Self {
+            load_cameras: false,
+            vertex_weld_tolerance: 0.0,
+            linear_vertex_colors: false,
+            generated_normals: GeneratedNormals::default(),
+            ignore_axis_system: false,
+            unit_in_meters: 1.0,
+            bake_unit_scale: false,
+            lenient: false,
+            external_textures_as_assets: false,
+        }
+    }
+}
+
+/// How the `FbxLoader` computes the normals of meshes without normals,
+/// see [`FbxLoaderSettings::generated_normals`].
+///
+/// Except for `Flat`, the smoothing groups and hard edges of the mesh
+/// are respected when the FBX file has them.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum GeneratedNormals {
+    /// Each triangle uses its own normal, giving a faceted look.
+    Flat,
+    /// Average the normals of the polygons sharing a vertex.
+    #[default]
+    Smooth,
+    /// Average the normals of the polygons sharing a vertex, unless
+    /// the angle between them is larger than this angle, in radians.
+    AngleThreshold(f32),
+}
+
+/// Sent when a [`FbxScene`] finished loading, or was reloaded.
+///
+/// This is a convenient way to check the [`FbxScene::diagnostics`] of
+/// loaded files, without waiting on each handle.
+#[derive(Clone, Debug)]
+pub struct FbxSceneLoaded {
+    /// A weak handle to the loaded scene.
+    pub scene: Handle<FbxScene>,
+    /// The [`FbxScene::diagnostics`] of the loaded scene.
+    pub diagnostics: Vec<FbxDiagnostic>,
+}
+
+fn send_scene_loaded(
+    mut asset_events: EventReader<AssetEvent<FbxScene>>,
+    mut loaded_events: EventWriter<FbxSceneLoaded>,
+    scenes: Res<Assets<FbxScene>>,
+) {
+    for event in asset_events.iter() {
+        let (AssetEvent::Created { handle } | AssetEvent::Modified { handle }) = event else {
+            continue;
+        };
+        if let Some(scene) = scenes.get(handle) {
+            loaded_events.send(FbxSceneLoaded {
+                scene: handle.clone_weak(),
+                diagnostics: scene.diagnostics.clone(),
+            });
+        }
+    }
+}
+
 impl Plugin for FbxPlugin {
     fn build(&self, app: &mut App) {
         app.init_asset_loader::<FbxLoader>()
             .add_asset::<FbxMesh>()
-            .add_asset::<FbxScene>();
+            .add_asset::<FbxScene>()
+            .add_event::<FbxSceneLoaded>()
+            .add_system(send_scene_loaded);
     }
 }
@@ -0,0 +1,140 @@
+//! Convert FBX `Light` node attributes into bevy lights.
+
+// a bit of trivia on how lights are encoded in FBX:
+// - Like cameras, a light is a Model node (subclass "Light") with a
+//   NodeAttribute (also subclass "Light") holding the light properties.
+// - Spot and directional lights point along the -Y axis of their node.
+//   Bevy lights point along -Z, so we need to add a rotation.
+// - `Intensity` is a percentage, 100 being the "default" intensity,
+//   there is no physical unit attached to it.
+// - `InnerAngle` and `OuterAngle` are the full cone angles in degrees,
+//   while bevy expects the angle between the cone axis and its edge in radians.
+//
+// See http://docs.autodesk.com/FBX/2014/ENU/FBX-SDK-Documentation/cpp_ref/class_fbx_light.html
+use std::f32::consts::FRAC_PI_2;
+
+use anyhow::{bail, Result};
+use bevy::prelude::{Color, DirectionalLight, PointLight, Quat, SpotLight, Transform};
+use fbxcel_dom::v7400::object::{
+    model::ModelHandle, nodeattribute::TypedNodeAttributeHandle, property::ObjectProperties,
+};
+use rgb::RGB;
+
+use crate::{
+    data::{FbxDiagnostic, FbxLight},
+    utils::fbx_extend::{Loadable, ModelHandleExt},
+};
+
+/// Lumens of a point or spot light with an FBX `Intensity` of 100.
+///
+/// This is the default intensity of bevy's `PointLight`.
+const POINT_LUMENS_PER_INTENSITY_UNIT: f32 = 800.0 / 100.0;
+/// Lux of a directional light with an FBX `Intensity` of 100.
+///
+/// This is the default illuminance of bevy's `DirectionalLight`.
+const DIRECTIONAL_LUX_PER_INTENSITY_UNIT: f32 = 100_000.0 / 100.0;
+/// Range, in bevy units, of lights that do not decay with distance.
+///
+/// Bevy lights always decay with the inverse square of the distance,
+/// this at least avoids cutting them off early.
+const NO_DECAY_RANGE: f32 = 1000.0;
+
+/// The rotation to apply to a bevy light so that it points in the
+/// same direction as the FBX light node it is a child of.
+pub(crate) fn light_correction() -> Transform {
+    Transform::from_rotation(Quat::from_rotation_x(-FRAC_PI_2))
+}
+
+/// Convert a FBX light node into a bevy light.
+///
+/// - `Intensity` is converted to lumens (point and spot lights) or lux
+///   (directional lights) so that the FBX default intensity of 100 is the bevy
+///   default intensity, the conversion is linear.
+/// - The `range` of point and spot lights is `FarAttenuationEnd` if `EnableFarAttenuation`
+///   is set. Otherwise, if `DecayType` is `None`, the range is very large,
+///   and for other decay types, we keep the bevy default range.
+///   Bevy only supports inverse square decay, so `Linear` and `Cubic` decays are
+///   approximated as quadratic.
+///
+/// `world_scale` is the scale of the FBX scene root, used to convert
+/// distances from FBX to bevy units.
+pub(crate) fn load_light(
+    model: ModelHandle,
+    world_scale: f32,
+    diagnostics: &mut Vec<FbxDiagnostic>,
+) -> Result<FbxLight> {
+    let Some(TypedNodeAttributeHandle::Light(attribute)) = model.node_attribute() else {
+        bail!("Light node without light attribute");
+    };
+    let p = attribute.properties_by_native_typename("FbxLight");
+    fn load<T: Loadable>(p: ObjectProperties, attribute: &str, default: T) -> T {
+        T::get_property(p, attribute).unwrap_or(default)
+    }
+    let RGB { r, g, b } = load(p, "Color", RGB::new(1.0_f32, 1.0, 1.0));
+    let color = Color::rgb(r, g, b);
+    let intensity = load(p, "Intensity", 100.0_f64) as f32;
+    let shadows_enabled = load(p, "CastShadows", true);
+
+    let range = if load(p, "EnableFarAttenuation", false) {
+        load(p, "FarAttenuationEnd", 0.0_f64) as f32 * world_scale
+    } else {
+        match load(p, "DecayType", 0_i32) {
+            0 => NO_DECAY_RANGE,
+            _ => PointLight::default().range,
+        }
+    };
+    let light_type = load(p, "LightType", 0_i32);
+    let light = match light_type {
+        1 => FbxLight::Directional(DirectionalLight {
+            color,
+            illuminance: intensity * DIRECTIONAL_LUX_PER_INTENSITY_UNIT,
+            shadows_enabled,
+            ..Default::default()
+        }),
+        2 => {
+            let outer_angle = load(p, "OuterAngle", 45.0_f64) as f32;
+            let inner_angle = load(p, "InnerAngle", 0.0_f64) as f32;
+            let half_angle_radians =
+                |full_angle: f32| (full_angle.to_radians() / 2.0).min(FRAC_PI_2);
+            let outer_angle = half_angle_radians(outer_angle);
+            FbxLight::Spot(SpotLight {
+                color,
+                intensity: intensity * POINT_LUMENS_PER_INTENSITY_UNIT,
+                range,
+                shadows_enabled,
+                inner_angle: half_angle_radians(inner_angle).min(outer_angle),
+                outer_angle,
+                ..Default::default()
+            })
+        }
+        light_type => {
+            let diagnostic = match light_type {
+                0 => None,
+                3 | 4 => Some(FbxDiagnostic::warning(
+                    "Area and volume lights are not supported, using a point light",
+                )),
+                _ => Some(FbxDiagnostic::info(format!(
+                    "Unknown light type {light_type}, using a point light"
+                ))),
+            };
+            if let Some(diagnostic) = diagnostic {
+                diagnostic
+                    .with_object(model.object_id(), model.name())
+                    .report(diagnostics);
+            }
+            FbxLight::Point(PointLight {
+                color,
+                intensity: intensity * POINT_LUMENS_PER_INTENSITY_UNIT,
+                range,
+                shadows_enabled,
+                ..Default::default()
+            })
+        }
+    };
+    Ok(light)
+}
+
+/// Whether `model` is a light node.
+pub(crate) fn is_light(model: &ModelHandle) -> bool {
+    model.subclass() == "Light"
+}
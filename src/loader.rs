@@ -1,33 +1,49 @@
-use std::path::Path;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+    sync::Arc,
+};
 
 use anyhow::{anyhow, bail, Context};
 use bevy::{
-    asset::{AssetLoader, BoxedFuture, LoadContext, LoadedAsset},
+    asset::{AssetLoader, AssetPath, BoxedFuture, LoadContext, LoadedAsset},
     math::{DVec2, DVec3, Vec2},
     prelude::{
-        debug, error, info, trace, BuildWorldChildren, FromWorld, Handle, Image, Mesh, Name,
-        PbrBundle, Scene, StandardMaterial, Transform, TransformBundle, VisibilityBundle, World,
-        WorldChildBuilder,
+        debug, error, info, trace, warn, BuildWorldChildren, Camera, Camera3dBundle, Color,
+        DirectionalLightBundle, Entity, FromWorld, Handle, Image, Mat3, Mat4, Mesh, Name,
+        PbrBundle, PointLightBundle, Projection, Scene, SpotLightBundle, StandardMaterial,
+        Transform, TransformBundle, VisibilityBundle, World, WorldChildBuilder,
     },
     render::{
-        mesh::{Indices, PrimitiveTopology, VertexAttributeValues},
-        render_resource::{AddressMode, SamplerDescriptor},
+        mesh::{
+            skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+            Indices, MeshVertexAttribute, PrimitiveTopology, VertexAttributeValues,
+        },
+        render_resource::{
+            AddressMode, Extent3d, SamplerDescriptor, TextureDimension, TextureFormat,
+        },
         renderer::RenderDevice,
         texture::{CompressedImageFormats, ImageSampler, ImageType},
     },
-    utils::HashMap,
+    utils::{HashMap, HashSet},
 };
 use fbxcel_dom::{
     any::AnyDocument,
+    fbxcel::low::FbxHeader,
     v7400::{
-        data::{mesh::layer::TypedLayerElementHandle, texture::WrapMode},
+        data::{
+            mesh::{layer::TypedLayerElementHandle, ControlPointIndex, TriangleVertices},
+            texture::WrapMode,
+        },
         object::{
             self,
+            deformer::{SkinHandle, TypedSubDeformerHandle},
             model::{ModelHandle, TypedModelHandle},
             texture::TextureHandle,
             ObjectId, TypedObjectHandle,
         },
-        Document,
+        Document, Loader as DocumentLoader,
     },
 };
 
@@ -36,27 +52,116 @@ use bevy::log::info_span;
 use glam::Vec3;
 
 use crate::{
-    data::{FbxMesh, FbxObject, FbxScene},
-    fbx_transform::FbxTransform,
-    utils::fbx_extend::{GlobalSettingsExt, ModelTreeRootExt},
-    utils::triangulate,
-    MaterialLoader,
+    ascii, camera,
+    data::{
+        FbxDiagnostic, FbxLight, FbxMesh, FbxMorphDeltas, FbxMorphTarget, FbxObject, FbxScene,
+        FbxSkin, FbxTextureTransform,
+    },
+    fbx_transform::{self, FbxTransform},
+    light,
+    material_loader::LOAD_FALLBACK,
+    texture_resolver::{TextureFileNames, TextureResolver},
+    utils::fbx_extend::{
+        BlendShapeChannelHandleExt, ClusterHandleExt, GlobalSettingsExt, MaterialHandleExt,
+        ModelTreeRootExt, ShapeHandleExt,
+    },
+    utils::{
+        layer::{RawLayerElement, TriangleCorners},
+        normals, triangulate, weld,
+    },
+    v6100, FbxError, FbxLoaderSettings, MaterialLoader,
 };
 
-/// Bevy is kinda "meters" based while FBX (or rather: stuff exported by maya) is in "centimeters"
+/// Bevy is kinda "meters" based while the FBX `UnitScaleFactor` is in "centimeters"
 /// Although it doesn't mean much in practice.
 const FBX_TO_BEVY_SCALE_FACTOR: f32 = 0.01;
 
+/// [`Name`] of the root entity of the spawned scene.
+const SCENE_ROOT_NAME: &str = "Fbx scene root";
+
+/// `Model` subclasses the loader knows what to do with, other nodes are
+/// only kept as transforms when they have supported descendants.
+const SUPPORTED_MODEL_SUBCLASSES: &[&str] = &[
+    "Mesh", "Null", "Root", "Limb", "LimbNode", "Camera", "Light",
+];
+
+/// Maximum number of joints affecting a single vertex in bevy.
+const MAX_VERTEX_INFLUENCES: usize = 4;
+
+/// Per control point joint indices and weights of a skinned mesh,
+/// as expected by `Mesh::ATTRIBUTE_JOINT_INDEX` and `Mesh::ATTRIBUTE_JOINT_WEIGHT`.
+struct SkinWeights {
+    joint_indices: Vec<[u16; MAX_VERTEX_INFLUENCES]>,
+    joint_weights: Vec<[f32; MAX_VERTEX_INFLUENCES]>,
+}
+
+/// The vertex attributes the UV sets of a mesh are stored in, in order.
+const UV_ATTRIBUTES: [MeshVertexAttribute; 4] = [
+    Mesh::ATTRIBUTE_UV_0,
+    crate::ATTRIBUTE_UV_1,
+    crate::ATTRIBUTE_UV_2,
+    crate::ATTRIBUTE_UV_3,
+];
+
+/// Per control point offsets of a blend shape.
+struct MorphShape {
+    name: String,
+    default_weight: f32,
+    positions: Vec<Vec3>,
+    normals: Vec<Vec3>,
+}
+
+/// Where the image of a texture comes from.
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum ImageKey {
+    /// The asset paths an external image file may be at, in order.
+    Paths(Vec<PathBuf>),
+    /// The hash of the `Content` of an embedded image.
+    Content(u64),
+    /// The texture object, when its image can't be found.
+    Object(ObjectId),
+}
+
+/// Textures with the same key share the same bevy `Image`.
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct TextureKey {
+    image: ImageKey,
+    is_srgb: bool,
+    address_modes: Option<[AddressMode; 2]>,
+}
+
+/// The parts of a [`FbxMesh`] read from its geometry.
+struct LoadedGeometry {
+    bevy_mesh_handles: Vec<Handle<Mesh>>,
+    morph_targets: Vec<FbxMorphTarget>,
+    uv_sets: Vec<String>,
+}
+
 pub struct Loader<'b, 'w> {
     scene: FbxScene,
     load_context: &'b mut LoadContext<'w>,
     suported_compressed_formats: CompressedImageFormats,
     material_loaders: Vec<MaterialLoader>,
+    texture_resolver: Arc<dyn TextureResolver>,
+    settings: FbxLoaderSettings,
+    /// Scale applied to positions and translations, when
+    /// [`FbxLoaderSettings::bake_unit_scale`] is enabled.
+    unit_scale: f32,
+    /// The texture UV transform to bake in the meshes using each material,
+    /// see [`MaterialLoader::baked_uv_transform`].
+    uv_transforms: HashMap<Handle<StandardMaterial>, FbxTextureTransform>,
+    /// The images loaded with the `AssetServer`,
+    /// see [`FbxLoaderSettings::external_textures_as_assets`].
+    texture_dependencies: Vec<AssetPath<'static>>,
+    /// The image of each loaded texture, see [`TextureKey`].
+    texture_images: HashMap<TextureKey, Handle<Image>>,
 }
 
 pub struct FbxLoader {
     supported: CompressedImageFormats,
     material_loaders: Vec<MaterialLoader>,
+    texture_resolver: Arc<dyn TextureResolver>,
+    settings: FbxLoaderSettings,
 }
 impl FromWorld for FbxLoader {
     fn from_world(world: &mut World) -> Self {
@@ -66,9 +171,13 @@ impl FromWorld for FbxLoader {
             None => CompressedImageFormats::all(),
         };
         let loaders: crate::FbxMaterialLoaders = world.get_resource().cloned().unwrap_or_default();
+        let resolver: crate::FbxTextureResolver = world.get_resource().cloned().unwrap_or_default();
+        let settings = world.get_resource().cloned().unwrap_or_default();
         Self {
             supported,
             material_loaders: loaders.0,
+            texture_resolver: resolver.0,
+            settings,
         }
     }
 }
@@ -79,24 +188,33 @@ impl AssetLoader for FbxLoader {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, anyhow::Result<()>> {
         Box::pin(async move {
-            let cursor = std::io::Cursor::new(bytes);
-            let reader = std::io::BufReader::new(cursor);
-            let maybe_doc =
-                AnyDocument::from_seekable_reader(reader).expect("Failed to load document");
-            if let AnyDocument::V7400(_ver, doc) = maybe_doc {
-                let loader =
-                    Loader::new(self.supported, self.material_loaders.clone(), load_context);
-                let potential_error = loader
-                    .load(*doc)
-                    .await
-                    .with_context(|| format!("failed to load {:?}", load_context.path()));
-                if let Err(err) = potential_error {
-                    error!("{err:?}");
+            let result = match load_document(bytes) {
+                Ok((doc, diagnostics)) => {
+                    let mut loader = Loader::new(
+                        self.supported,
+                        self.material_loaders.clone(),
+                        self.texture_resolver.clone(),
+                        self.settings.clone(),
+                        load_context,
+                    );
+                    loader.scene.diagnostics = diagnostics;
+                    // Errors that are not already a `FbxError` are wrapped in `FbxError::Other`.
+                    loader
+                        .load(doc)
+                        .await
+                        .map_err(|err| match err.downcast_ref::<FbxError>() {
+                            Some(_) => err,
+                            None => FbxError::Other(err).into(),
+                        })
                 }
-                Ok(())
-            } else {
-                Err(anyhow!("TODO: better error handling in fbx loader"))
+                Err(err) => Err(err.into()),
+            };
+            let result =
+                result.with_context(|| format!("failed to load {:?}", load_context.path()));
+            if let Err(err) = &result {
+                error!("{err:?}");
             }
+            result
         })
     }
     fn extensions(&self) -> &[&str] {
@@ -104,36 +222,126 @@ impl AssetLoader for FbxLoader {
     }
 }
 
+/// Parse a binary or ASCII FBX file, along with the problems found
+/// while converting it to the FBX 7.4 document layout.
+fn load_document(bytes: &[u8]) -> Result<(Document, Vec<FbxDiagnostic>), FbxError> {
+    let mut diagnostics = Vec::new();
+    if ascii::is_ascii(bytes) {
+        let tree = ascii::parse(bytes).map_err(FbxError::Parse)?;
+        let version = ascii::version(&tree)
+            .ok_or_else(|| FbxError::Parse(anyhow!("No FBXVersion in ASCII FBX file")))?;
+        let doc = match version / 1000 {
+            6 => v6100::load_tree(&tree, &mut diagnostics).map_err(FbxError::Parse)?,
+            7 => DocumentLoader::new()
+                .load_from_tree(tree)
+                .map_err(|err| FbxError::Parse(err.into()))?,
+            major => {
+                let minor = version % 1000 / 100;
+                return Err(FbxError::UnsupportedVersion { major, minor });
+            }
+        };
+        return Ok((doc, diagnostics));
+    }
+    if FbxHeader::load(bytes).is_ok_and(v6100::is_v6) {
+        let doc = v6100::load_binary(bytes, &mut diagnostics).map_err(FbxError::Parse)?;
+        return Ok((doc, diagnostics));
+    }
+    let reader = std::io::BufReader::new(std::io::Cursor::new(bytes));
+    match AnyDocument::from_seekable_reader(reader) {
+        Ok(AnyDocument::V7400(_ver, doc)) => Ok((*doc, diagnostics)),
+        Ok(_) => Err(FbxError::Parse(anyhow!("Unknown FBX document kind"))),
+        Err(err) => Err(parse_error(err)),
+    }
+}
+
+/// Convert a `fbxcel_dom` document loading error into a [`FbxError`].
+fn parse_error(err: fbxcel_dom::any::Error) -> FbxError {
+    use fbxcel_dom::{
+        any::Error as DomError,
+        fbxcel::{pull_parser::any::Error as ParserError, tree::any::Error as TreeError},
+    };
+    match err {
+        DomError::UnsupportedVersion(version)
+        | DomError::Tree(TreeError::ParserCreation(ParserError::UnsupportedVersion(version))) => {
+            let (major, minor) = version.major_minor();
+            FbxError::UnsupportedVersion { major, minor }
+        }
+        err => FbxError::Parse(err.into()),
+    }
+}
+
 fn spawn_scene(
-    fbx_file_scale: f32,
+    root_transform: Transform,
     roots: &[ObjectId],
     hierarchy: &HashMap<ObjectId, FbxObject>,
     models: &HashMap<ObjectId, FbxMesh>,
+    cameras: &HashMap<ObjectId, Projection>,
+    lights: &HashMap<ObjectId, FbxLight>,
+    diagnostics: &mut Vec<FbxDiagnostic>,
 ) -> Scene {
     #[cfg(feature = "profile")]
     let _generate_scene_span = info_span!("generate_scene").entered();
 
     let mut scene_world = World::default();
+    let mut spawned = SpawnedEntities::default();
     scene_world
         .spawn((
             VisibilityBundle::default(),
-            TransformBundle::from_transform(Transform::from_scale(
-                Vec3::ONE * FBX_TO_BEVY_SCALE_FACTOR * fbx_file_scale,
-            )),
-            Name::new("Fbx scene root"),
+            TransformBundle::from_transform(root_transform),
+            Name::new(SCENE_ROOT_NAME),
         ))
         .with_children(|commands| {
             for root in roots {
-                spawn_scene_rec(*root, commands, hierarchy, models);
+                spawn_scene_rec(
+                    *root,
+                    commands,
+                    hierarchy,
+                    models,
+                    cameras,
+                    lights,
+                    &mut spawned,
+                );
             }
         });
+    // Joints can be anywhere in the hierarchy, so we can only
+    // add the `SkinnedMesh` components once all nodes are spawned.
+    for (entity, mesh, skin) in spawned.skinned_meshes {
+        let joints: Option<Vec<_>> = skin
+            .joints
+            .iter()
+            .map(|joint| spawned.nodes.get(joint).copied())
+            .collect();
+        let Some(joints) = joints else {
+            let message =
+                "Some joints of the skinned mesh are not in the scene, it won't be animated";
+            let name = models.get(&mesh).and_then(|mesh| mesh.name.as_deref());
+            FbxDiagnostic::warning(message)
+                .with_object(mesh, name)
+                .report(diagnostics);
+            continue;
+        };
+        scene_world.entity_mut(entity).insert(SkinnedMesh {
+            inverse_bindposes: skin.inverse_bindposes.clone(),
+            joints,
+        });
+    }
     Scene::new(scene_world)
 }
-fn spawn_scene_rec(
+/// Entities spawned by [`spawn_scene_rec`] that need further processing.
+#[derive(Default)]
+struct SpawnedEntities<'a> {
+    nodes: HashMap<ObjectId, Entity>,
+    /// The entity, mesh node and skin of each skinned mesh primitive.
+    skinned_meshes: Vec<(Entity, ObjectId, &'a FbxSkin)>,
+}
+fn spawn_scene_rec<'a>(
     current: ObjectId,
     commands: &mut WorldChildBuilder,
     hierarchy: &HashMap<ObjectId, FbxObject>,
-    models: &HashMap<ObjectId, FbxMesh>,
+    models: &'a HashMap<ObjectId, FbxMesh>,
+    cameras: &HashMap<ObjectId, Projection>,
+    lights: &HashMap<ObjectId, FbxLight>,
+    spawned: &mut SpawnedEntities<'a>,
 ) {
     let current_node = match hierarchy.get(&current) {
         Some(node) => node,
@@ -142,25 +350,63 @@ fn spawn_scene_rec(
     let mut entity = commands.spawn((
         VisibilityBundle::default(),
         TransformBundle::from_transform(current_node.transform),
+        current_node.entity_name(current),
     ));
-    if let Some(name) = &current_node.name {
-        entity.insert(Name::new(name.clone()));
-    }
+    spawned.nodes.insert(current, entity.id());
     entity.with_children(|commands| {
         if let Some(mesh) = models.get(&current) {
             for (mat, bevy_mesh) in mesh.materials.iter().zip(&mesh.bevy_mesh_handles) {
                 let mut entity = commands.spawn(PbrBundle {
                     mesh: bevy_mesh.clone(),
                     material: mat.clone(),
+                    transform: mesh.transform,
                     ..Default::default()
                 });
                 if let Some(name) = mesh.name.as_ref() {
                     entity.insert(Name::new(name.clone()));
                 }
+                if let Some(skin) = &mesh.skin {
+                    spawned.skinned_meshes.push((entity.id(), current, skin));
+                }
             }
         }
+        if let Some(projection) = cameras.get(&current) {
+            commands.spawn(Camera3dBundle {
+                camera: Camera {
+                    is_active: false,
+                    ..Default::default()
+                },
+                projection: projection.clone(),
+                transform: camera::camera_correction(),
+                ..Default::default()
+            });
+        }
+        if let Some(light) = lights.get(&current) {
+            let transform = light::light_correction();
+            match light.clone() {
+                FbxLight::Point(point_light) => commands.spawn(PointLightBundle {
+                    point_light,
+                    transform,
+                    ..Default::default()
+                }),
+                FbxLight::Spot(spot_light) => commands.spawn(SpotLightBundle {
+                    spot_light,
+                    transform,
+                    ..Default::default()
+                }),
+                FbxLight::Directional(directional_light) => {
+                    commands.spawn(DirectionalLightBundle {
+                        directional_light,
+                        transform,
+                        ..Default::default()
+                    })
+                }
+            };
+        }
         for node_id in &current_node.children {
-            spawn_scene_rec(*node_id, commands, hierarchy, models);
+            spawn_scene_rec(
+                *node_id, commands, hierarchy, models, cameras, lights, spawned,
+            );
         }
     });
 }
@@ -169,16 +415,55 @@ impl<'b, 'w> Loader<'b, 'w> {
     fn new(
         formats: CompressedImageFormats,
         loaders: Vec<MaterialLoader>,
+        texture_resolver: Arc<dyn TextureResolver>,
+        settings: FbxLoaderSettings,
         load_context: &'b mut LoadContext<'w>,
     ) -> Self {
         Self {
             scene: FbxScene::default(),
             load_context,
             material_loaders: loaders,
+            texture_resolver,
             suported_compressed_formats: formats,
+            settings,
+            unit_scale: 1.0,
+            uv_transforms: HashMap::default(),
+            texture_dependencies: Vec::new(),
+            texture_images: HashMap::default(),
+        }
+    }
+
+    /// In [lenient] mode, record the error of `result` as a diagnostic
+    /// about `object`, and return `None` rather than failing the whole load.
+    ///
+    /// [lenient]: FbxLoaderSettings::lenient
+    fn recover<T>(
+        &mut self,
+        object: ObjectId,
+        name: Option<&str>,
+        result: anyhow::Result<T>,
+    ) -> anyhow::Result<Option<T>> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(err) if self.settings.lenient => {
+                FbxDiagnostic::error(format!("{err:#}"))
+                    .with_object(object, name)
+                    .report(&mut self.scene.diagnostics);
+                Ok(None)
+            }
+            Err(err) => Err(err),
         }
     }
 
+    /// `transform` with its translation scaled by `unit_scale`.
+    ///
+    /// For a mesh or skin transform, this is as if the transform was
+    /// computed with vertex positions and translations already scaled.
+    fn scale_space(&self, transform: Mat4) -> Mat4 {
+        let scale = Mat4::from_scale(Vec3::splat(self.unit_scale));
+        scale * transform * scale.inverse()
+    }
+
     async fn load(mut self, doc: Document) -> anyhow::Result<()> {
         info!(
             "Started loading scene {}#FbxScene",
@@ -191,18 +476,138 @@ impl<'b, 'w> Loader<'b, 'w> {
             .global_settings()
             .and_then(|g| g.fbx_scale())
             .unwrap_or(1.0);
+        let axis_system = match doc.global_settings().and_then(|g| g.axis_system()) {
+            _ if self.settings.ignore_axis_system => Mat3::IDENTITY,
+            Some(axis_system) => axis_system,
+            None => {
+                FbxDiagnostic::info("No valid axis system in FBX file, assuming Y-up")
+                    .report(&mut self.scene.diagnostics);
+                Mat3::IDENTITY
+            }
+        };
+        let mut kept_nodes: HashSet<_> = doc
+            .objects()
+            .filter_map(|obj| match obj.get_typed() {
+                TypedObjectHandle::SubDeformer(TypedSubDeformerHandle::Cluster(cluster)) => {
+                    cluster.bone()
+                }
+                _ => None,
+            })
+            .map(|bone| bone.object_id())
+            .collect();
+        let unit_in_meters = match self.settings.unit_in_meters {
+            unit if unit > 0.0 => unit,
+            unit => {
+                warn!("Invalid FbxLoaderSettings::unit_in_meters: {unit}, using meters");
+                1.0
+            }
+        };
+        let world_scale = FBX_TO_BEVY_SCALE_FACTOR * fbx_scale as f32 / unit_in_meters;
+        if self.settings.bake_unit_scale {
+            self.unit_scale = world_scale;
+        }
+        for obj in doc.objects() {
+            let TypedObjectHandle::Model(model) = obj.get_typed() else {
+                continue;
+            };
+            let subclass = model.subclass();
+            if !SUPPORTED_MODEL_SUBCLASSES.contains(&subclass) {
+                FbxDiagnostic::warning(format!("Unsupported node type {subclass:?}, ignoring it"))
+                    .with_object(obj.object_id(), obj.name())
+                    .report(&mut self.scene.diagnostics);
+            }
+            if self.settings.load_cameras && camera::is_camera(&model) {
+                let projection = camera::load_camera(*model, self.unit_scale)
+                    .with_context(|| format!("Failed to load camera {:?}", obj.name()));
+                if let Some(projection) = self.recover(obj.object_id(), obj.name(), projection)? {
+                    self.scene.cameras.insert(obj.object_id(), projection);
+                    kept_nodes.insert(obj.object_id());
+                }
+            } else if light::is_light(&model) {
+                let diagnostics = &mut self.scene.diagnostics;
+                let light = light::load_light(*model, world_scale, diagnostics)
+                    .with_context(|| format!("Failed to load light {:?}", obj.name()));
+                if let Some(light) = self.recover(obj.object_id(), obj.name(), light)? {
+                    self.scene.lights.insert(obj.object_id(), light);
+                    kept_nodes.insert(obj.object_id());
+                }
+            }
+        }
         let roots = doc.model_roots();
         for root in &roots {
-            traverse_hierarchy(*root, &mut hierarchy);
+            let diagnostics = &mut self.scene.diagnostics;
+            let lenient = self.settings.lenient;
+            traverse_hierarchy(*root, &kept_nodes, lenient, &mut hierarchy, diagnostics)?;
+        }
+        for node in hierarchy.values_mut() {
+            node.transform.translation *= self.unit_scale;
         }
 
         for obj in doc.objects() {
             if let TypedObjectHandle::Model(TypedModelHandle::Mesh(mesh)) = obj.get_typed() {
-                meshes.insert(obj.object_id(), self.load_mesh(mesh).await?);
+                let mesh = self.load_mesh(mesh).await;
+                let mesh = mesh.with_context(|| format!("Failed to load mesh {:?}", obj.name()));
+                if let Some(mesh) = self.recover(obj.object_id(), obj.name(), mesh)? {
+                    meshes.insert(obj.object_id(), mesh);
+                }
             }
         }
         let roots: Vec<_> = roots.into_iter().map(|obj| obj.object_id()).collect();
-        let scene = spawn_scene(fbx_scale as f32, &roots, &hierarchy, &meshes);
+
+        #[cfg(feature = "animation")]
+        let frame_rate = doc
+            .global_settings()
+            .and_then(|g| g.frame_rate())
+            .unwrap_or(crate::animation::DEFAULT_FRAME_RATE);
+        #[cfg(feature = "animation")]
+        for stack in doc.objects().filter(crate::animation::is_animation_stack) {
+            let label = match stack.name() {
+                Some(name) if !name.is_empty() => format!("FbxAnimation@{name}"),
+                _ => format!("FbxAnimation{}", stack.object_id().raw()),
+            };
+            debug!("Loading FBX animation: {label}");
+            let root_name = Name::new(SCENE_ROOT_NAME);
+            let clip = crate::animation::load_animation_stack(
+                &doc,
+                stack,
+                &root_name,
+                &roots,
+                &hierarchy,
+                self.unit_scale,
+                frame_rate,
+                &mut self.scene.diagnostics,
+            )
+            .with_context(|| format!("Failed to load animation {label}"));
+            let Some(clip) = self.recover(stack.object_id(), stack.name(), clip)? else {
+                continue;
+            };
+            let handle = self
+                .load_context
+                .set_labeled_asset(&label, LoadedAsset::new(clip));
+            if let Some(name) = stack.name() {
+                self.scene
+                    .named_animations
+                    .insert(name.to_owned(), handle.clone());
+            }
+            self.scene.animations.push(handle);
+        }
+
+        let root_scale = if self.settings.bake_unit_scale {
+            1.0
+        } else {
+            world_scale
+        };
+        let world_scale = Mat3::from_diagonal(Vec3::splat(root_scale));
+        let root_transform = Transform::from_matrix(Mat4::from_mat3(world_scale * axis_system));
+        let scene = spawn_scene(
+            root_transform,
+            &roots,
+            &hierarchy,
+            &meshes,
+            &self.scene.cameras,
+            &self.scene.lights,
+            &mut self.scene.diagnostics,
+        );
 
         let load_context = &mut self.load_context;
         load_context.set_labeled_asset("Scene", LoadedAsset::new(scene));
@@ -210,7 +615,8 @@ impl<'b, 'w> Loader<'b, 'w> {
         let mut scene = self.scene;
         scene.hierarchy = hierarchy.clone();
         scene.roots = roots;
-        load_context.set_labeled_asset("FbxScene", LoadedAsset::new(scene));
+        let scene = LoadedAsset::new(scene).with_dependencies(self.texture_dependencies);
+        load_context.set_labeled_asset("FbxScene", scene);
         info!(
             "Successfully loaded scene {}#FbxScene",
             load_context.path().to_string_lossy(),
@@ -221,8 +627,11 @@ impl<'b, 'w> Loader<'b, 'w> {
     fn load_bevy_mesh(
         &mut self,
         mesh_obj: object::geometry::MeshHandle,
-        num_materials: usize,
-    ) -> anyhow::Result<Vec<Handle<Mesh>>> {
+        skin_weights: Option<&SkinWeights>,
+        morph_shapes: &[MorphShape],
+        material_uv_sets: &[Option<&str>],
+        material_uv_transforms: &[Option<FbxTextureTransform>],
+    ) -> anyhow::Result<LoadedGeometry> {
         let label = match mesh_obj.name() {
             Some(name) if !name.is_empty() => format!("FbxMesh@{name}/Primitive"),
             _ => format!("FbxMesh{}/Primitive", mesh_obj.object_id().raw()),
@@ -248,14 +657,15 @@ impl<'b, 'w> Loader<'b, 'w> {
         #[cfg(feature = "profile")]
         drop(triangulate_mesh);
 
-        // TODO this seems to duplicate vertices from neighboring triangles. We shouldn't
-        // do that and instead set the indice attribute of the Mesh properly.
+        // NOTE: we first create one vertex per triangle corner, identical vertices
+        // are merged afterward with `weld_vertices`.
+        let unit_scale = self.unit_scale;
         let get_position = |pos: Option<_>| -> Result<_, anyhow::Error> {
             let cpi = pos.ok_or_else(|| anyhow!("Failed to get control point index"))?;
             let point = polygon_vertices
                 .control_point(cpi)
                 .ok_or_else(|| anyhow!("Failed to get control point: cpi={:?}", cpi))?;
-            Ok(DVec3::from(point).as_vec3().into())
+            Ok((DVec3::from(point).as_vec3() * unit_scale).into())
         };
         let positions = triangle_pvi_indices
             .iter_control_point_indices()
@@ -265,18 +675,46 @@ impl<'b, 'w> Loader<'b, 'w> {
 
         debug!("Expand position lenght to {}", positions.len());
 
-        let layer = mesh_obj
-            .layers()
-            .next()
-            .ok_or_else(|| anyhow!("Failed to get layer"))?;
+        let skinning = skin_weights.map(|weights| {
+            let get_influence = |cpi: Option<ControlPointIndex>| {
+                let cpi = cpi?.to_u32() as usize;
+                Some((
+                    *weights.joint_indices.get(cpi)?,
+                    *weights.joint_weights.get(cpi)?,
+                ))
+            };
+            triangle_pvi_indices
+                .iter_control_point_indices()
+                .map(|cpi| get_influence(cpi).unwrap_or_default())
+                .unzip::<_, _, Vec<_>, Vec<_>>()
+        });
 
+        let mut morph_deltas: Vec<_> = morph_shapes
+            .iter()
+            .map(|shape| {
+                let get_delta = |deltas: &[Vec3], scale: f32, cpi: Option<ControlPointIndex>| {
+                    let delta = cpi.and_then(|cpi| deltas.get(cpi.to_u32() as usize));
+                    (delta.copied().unwrap_or_default() * scale).into()
+                };
+                let cpis = || triangle_pvi_indices.iter_control_point_indices();
+                FbxMorphDeltas {
+                    positions: cpis()
+                        .map(|i| get_delta(&shape.positions, unit_scale, i))
+                        .collect(),
+                    normals: cpis().map(|i| get_delta(&shape.normals, 1.0, i)).collect(),
+                }
+            })
+            .collect();
+
+        let num_materials = material_uv_sets.len();
         let indices_per_material = || -> Result<_, anyhow::Error> {
             if num_materials == 0 {
                 return Ok(None);
             };
             let mut indices_per_material = vec![Vec::new(); num_materials];
-            let materials = layer
-                .layer_element_entries()
+            let materials = mesh_obj
+                .layers()
+                .flat_map(|layer| layer.layer_element_entries())
                 .find_map(|entry| match entry.typed_layer_element() {
                     Ok(TypedLayerElementHandle::Material(handle)) => Some(handle),
                     _ => None,
@@ -284,34 +722,28 @@ impl<'b, 'w> Loader<'b, 'w> {
                 .ok_or_else(|| anyhow!("Materials not found for mesh {:?}", mesh_obj))?
                 .materials()
                 .context("Failed to get materials")?;
+            let mut skipped_corners = 0;
             for tri_vi in triangle_pvi_indices.triangle_vertex_indices() {
                 let local_material_index = materials
                     .material_index(&triangle_pvi_indices, tri_vi)
                     .context("Failed to get mesh-local material index")?
                     .to_u32();
-                indices_per_material
-                     .get_mut(local_material_index as usize)
-                     .ok_or_else(|| {
-                         anyhow!(
-                             "FbxMesh-local material index out of range: num_materials={:?}, got={:?}",
-                             num_materials,
-                             local_material_index
-                         )
-                     })?
-                     .push(tri_vi.to_usize() as u32);
+                match indices_per_material.get_mut(local_material_index as usize) {
+                    Some(indices) => indices.push(tri_vi.to_usize() as u32),
+                    None => skipped_corners += 1,
+                }
             }
-            Ok(Some(indices_per_material))
+            Ok(Some((indices_per_material, skipped_corners / 3)))
         };
-        let normals = {
-            let normals = layer
-                .layer_element_entries()
-                .find_map(|entry| match entry.typed_layer_element() {
-                    Ok(TypedLayerElementHandle::Normal(handle)) => Some(handle),
-                    _ => None,
-                })
-                .ok_or_else(|| anyhow!("Failed to get normals"))?
-                .normals()
-                .context("Failed to get normals")?;
+        let normal_element = mesh_obj
+            .layers()
+            .flat_map(|layer| layer.layer_element_entries())
+            .find_map(|entry| match entry.typed_layer_element() {
+                Ok(TypedLayerElementHandle::Normal(handle)) => Some(handle),
+                _ => None,
+            });
+        let normals = if let Some(normals) = normal_element {
+            let normals = normals.normals().context("Failed to get normals")?;
             let get_indices = |tri_vi| -> Result<_, anyhow::Error> {
                 let v = normals.normal(&triangle_pvi_indices, tri_vi)?;
                 Ok(DVec3::from(v).as_vec3().into())
@@ -321,78 +753,260 @@ impl<'b, 'w> Loader<'b, 'w> {
                 .map(get_indices)
                 .collect::<Result<Vec<_>, _>>()
                 .context("Failed to reconstruct normals vertices")?
+        } else {
+            FbxDiagnostic::warning("Geometry has no normals, generating them")
+                .with_object(mesh_obj.object_id(), mesh_obj.name())
+                .report(&mut self.scene.diagnostics);
+            let settings = self.settings.generated_normals;
+            normals::generate_normals(&mesh_obj, &triangle_pvi_indices, &positions, settings)?
         };
-        let uv = {
-            let uv = layer
-                .layer_element_entries()
-                .find_map(|entry| match entry.typed_layer_element() {
-                    Ok(TypedLayerElementHandle::Uv(handle)) => Some(handle),
-                    _ => None,
-                })
-                .ok_or_else(|| anyhow!("Failed to get UV"))?
-                .uv()?;
+        if normals.len() != positions.len() {
+            bail!(
+                "mismatched length of buffers: pos{} normals{}",
+                positions.len(),
+                normals.len(),
+            );
+        }
+        // Broken authored tangents are replaced by generated ones further down.
+        let tangents = match load_tangents(mesh_obj, &triangle_pvi_indices, &normals) {
+            Ok(tangents) => tangents,
+            Err(err) => {
+                let message = format!("Failed to read tangents, generating them: {err:#}");
+                FbxDiagnostic::warning(message)
+                    .with_object(mesh_obj.object_id(), mesh_obj.name())
+                    .report(&mut self.scene.diagnostics);
+                None
+            }
+        };
+        // UV sets may be spread across several layers, each UV layer element
+        // is identified by its `TypedIndex`.
+        let mut uv_elements: Vec<_> = mesh_obj
+            .layers()
+            .flat_map(|layer| layer.layer_element_entries())
+            .filter_map(|entry| match entry.typed_layer_element() {
+                Ok(TypedLayerElementHandle::Uv(handle)) => {
+                    Some((handle.typed_index().ok()?.to_u32(), handle))
+                }
+                _ => None,
+            })
+            .collect();
+        uv_elements.sort_by_key(|(index, _)| *index);
+        uv_elements.dedup_by_key(|(index, _)| *index);
+        if uv_elements.is_empty() {
+            FbxDiagnostic::warning("Geometry has no UVs, textures won't be applied to it")
+                .with_object(mesh_obj.object_id(), mesh_obj.name())
+                .report(&mut self.scene.diagnostics);
+        }
+        if uv_elements.len() > UV_ATTRIBUTES.len() {
+            let message = format!(
+                "Geometry has {} UV sets, only the first {} are loaded",
+                uv_elements.len(),
+                UV_ATTRIBUTES.len(),
+            );
+            FbxDiagnostic::warning(message)
+                .with_object(mesh_obj.object_id(), mesh_obj.name())
+                .report(&mut self.scene.diagnostics);
+            uv_elements.truncate(UV_ATTRIBUTES.len());
+        }
+        let mut uv_sets = Vec::with_capacity(uv_elements.len());
+        for (index, handle) in uv_elements {
+            let uv = handle.uv()?;
             let get_indices = |tri_vi| -> Result<_, anyhow::Error> {
                 let v = uv.uv(&triangle_pvi_indices, tri_vi)?;
                 let fbx_uv_space = DVec2::from(v).as_vec2();
                 let bevy_uv_space = fbx_uv_space * Vec2::new(1.0, -1.0) + Vec2::new(0.0, 1.0);
                 Ok(bevy_uv_space.into())
             };
-            triangle_pvi_indices
+            let uv: Vec<[f32; 2]> = triangle_pvi_indices
                 .triangle_vertex_indices()
                 .map(get_indices)
                 .collect::<Result<Vec<_>, _>>()
-                .context("Failed to reconstruct UV vertices")?
-        };
-
-        if uv.len() != positions.len() || uv.len() != normals.len() {
-            bail!(
-                "mismatched length of buffers: pos{} uv{} normals{}",
-                positions.len(),
-                uv.len(),
-                normals.len(),
-            );
+                .context("Failed to reconstruct UV vertices")?;
+            if uv.len() != positions.len() {
+                bail!(
+                    "mismatched length of buffers: pos{} uv{}",
+                    positions.len(),
+                    uv.len(),
+                );
+            }
+            let name = match handle.name() {
+                Ok(name) if !name.is_empty() => name.to_owned(),
+                _ => format!("UVSet{index}"),
+            };
+            uv_sets.push((name, uv));
         }
+        let uv_set_names: Vec<_> = uv_sets.iter().map(|(name, _)| name.clone()).collect();
 
-        // TODO: remove unused vertices from partial models
-        // this is complicated, as it also requires updating the indices.
+        // fbxcel-dom looks for a `ColorsIndex` node, exporters write `ColorIndex`.
+        let colors =
+            RawLayerElement::find_indexed(&mesh_obj, "LayerElementColor", "Colors", "ColorIndex")
+                .and_then(|colors| match colors {
+                    Some(colors) => colors
+                        .vec4s(&TriangleCorners::new(&triangle_pvi_indices)?)
+                        .map(Some),
+                    None => Ok(None),
+                });
+        let colors = match colors {
+            Ok(colors) => colors.map(|colors| {
+                let linear = self.settings.linear_vertex_colors;
+                let get_color = |[r, g, b, a]: [f32; 4]| {
+                    let color = if linear {
+                        Color::rgba_linear(r, g, b, a)
+                    } else {
+                        Color::rgba(r, g, b, a)
+                    };
+                    color.as_linear_rgba_f32()
+                };
+                colors.into_iter().map(get_color).collect::<Vec<_>>()
+            }),
+            Err(err) => {
+                let message = format!("Failed to read vertex colors, skipping them: {err:#}");
+                FbxDiagnostic::warning(message)
+                    .with_object(mesh_obj.object_id(), mesh_obj.name())
+                    .report(&mut self.scene.diagnostics);
+                None
+            }
+        };
 
         // A single mesh may have multiple materials applied to a different subset of
         // its vertices. In the following code, we create a unique mesh per material
-        // we found.
-        let full_mesh_indices: Vec<_> = triangle_pvi_indices
-            .triangle_vertex_indices()
-            .map(|t| t.to_usize() as u32)
-            .collect();
-        let all_indices = if let Some(per_materials) = indices_per_material()? {
-            per_materials
-        } else {
-            vec![full_mesh_indices.clone()]
+        // we found, only containing the vertices used by this material.
+        let all_indices = match indices_per_material()? {
+            Some((indices, 0)) => Some(indices),
+            Some((indices, skipped)) => {
+                let message = format!(
+                    "{skipped} triangles have a material index out of range \
+                    (the mesh has {num_materials} materials), skipping them"
+                );
+                FbxDiagnostic::warning(message)
+                    .with_object(mesh_obj.object_id(), mesh_obj.name())
+                    .report(&mut self.scene.diagnostics);
+                Some(indices)
+            }
+            None => None,
         };
 
-        debug!("Material count for {label}: {}", all_indices.len());
-
         let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
         mesh.insert_attribute(
             Mesh::ATTRIBUTE_POSITION,
             VertexAttributeValues::Float32x3(positions),
         );
-        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, VertexAttributeValues::Float32x2(uv));
+        for (attribute, (_, uv)) in UV_ATTRIBUTES.into_iter().zip(uv_sets) {
+            mesh.insert_attribute(attribute, VertexAttributeValues::Float32x2(uv));
+        }
         mesh.insert_attribute(
             Mesh::ATTRIBUTE_NORMAL,
             VertexAttributeValues::Float32x3(normals),
         );
-        mesh.set_indices(Some(Indices::U32(full_mesh_indices)));
-        mesh.generate_tangents()
-            .context("Failed to generate tangents")?;
+        if let Some(tangents) = tangents {
+            mesh.insert_attribute(
+                Mesh::ATTRIBUTE_TANGENT,
+                VertexAttributeValues::Float32x4(tangents),
+            );
+        }
+        if let Some(colors) = colors {
+            mesh.insert_attribute(
+                Mesh::ATTRIBUTE_COLOR,
+                VertexAttributeValues::Float32x4(colors),
+            );
+        }
+        if let Some((joint_indices, joint_weights)) = skinning {
+            mesh.insert_attribute(
+                Mesh::ATTRIBUTE_JOINT_INDEX,
+                VertexAttributeValues::Uint16x4(joint_indices),
+            );
+            mesh.insert_attribute(
+                Mesh::ATTRIBUTE_JOINT_WEIGHT,
+                VertexAttributeValues::Float32x4(joint_weights),
+            );
+        }
+
+        let deltas = morph_deltas
+            .iter()
+            .flat_map(|deltas| [&deltas.positions[..], &deltas.normals[..]]);
+        let deltas: Vec<_> = deltas.collect();
+        let tolerance = self.settings.vertex_weld_tolerance;
+        let welded = weld::weld_vertices(&mesh, &deltas, tolerance);
+        debug!(
+            "Welded {} vertices into {} for {label}",
+            welded.remap.len(),
+            welded.kept.len()
+        );
+        weld::select_vertices(&mut mesh, &welded.kept);
+        for deltas in &mut morph_deltas {
+            *deltas = select_deltas(deltas, &welded.kept);
+        }
+        // Triangle corner indices are the vertex indices before welding.
+        let all_indices = match all_indices {
+            Some(per_materials) => per_materials
+                .into_iter()
+                .map(|indices| indices.iter().map(|&i| welded.remap[i as usize]).collect())
+                .collect(),
+            None => vec![welded.remap],
+        };
+        debug!("Material count for {label}: {}", all_indices.len());
 
+        let mut morph_targets: Vec<_> = morph_shapes
+            .iter()
+            .map(|shape| FbxMorphTarget {
+                name: shape.name.clone(),
+                default_weight: shape.default_weight,
+                primitives: Vec::with_capacity(all_indices.len()),
+            })
+            .collect();
         let all_handles = all_indices
             .into_iter()
             .enumerate()
-            .map(|(i, material_indices)| {
-                debug!("Material {i} has {} vertices", material_indices.len());
+            .map(|(i, material_indices)| -> anyhow::Result<_> {
+                let (kept, material_indices) = weld::used_vertices(&material_indices);
+                debug!(
+                    "Material {i} has {} indices and {} vertices",
+                    material_indices.len(),
+                    kept.len()
+                );
 
                 let mut material_mesh = mesh.clone();
+                weld::select_vertices(&mut material_mesh, &kept);
                 material_mesh.set_indices(Some(Indices::U32(material_indices)));
+                for (target, deltas) in morph_targets.iter_mut().zip(&morph_deltas) {
+                    target.primitives.push(select_deltas(deltas, &kept));
+                }
+                // bevy's StandardMaterial only uses the first UV set, so we swap in
+                // the UV set the textures of this material are mapped to.
+                let material_uv_set = material_uv_sets.get(i).copied().flatten();
+                let uv_set = material_uv_set
+                    .and_then(|set| uv_set_names.iter().position(|name| name == set));
+                if let Some(uv_set) = uv_set.filter(|set| *set != 0) {
+                    let attribute = UV_ATTRIBUTES[uv_set].clone();
+                    let uv_0 = material_mesh.remove_attribute(Mesh::ATTRIBUTE_UV_0);
+                    let uv_n = material_mesh.remove_attribute(attribute.clone());
+                    if let (Some(uv_0), Some(uv_n)) = (uv_0, uv_n) {
+                        material_mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uv_n);
+                        material_mesh.insert_attribute(attribute, uv_0);
+                    }
+                    // Authored tangents follow the first UV set, not this one.
+                    material_mesh.remove_attribute(Mesh::ATTRIBUTE_TANGENT);
+                }
+                // Neither does it support texture UV transforms, so we apply it to the UVs.
+                let uv_transform = material_uv_transforms.get(i).copied().flatten();
+                let uvs = material_mesh.attribute_mut(Mesh::ATTRIBUTE_UV_0);
+                if let (Some(transform), Some(VertexAttributeValues::Float32x2(uvs))) =
+                    (uv_transform, uvs)
+                {
+                    let transform = transform.to_affine2();
+                    for uv in uvs.iter_mut() {
+                        let fbx_uv = transform.transform_point2(Vec2::new(uv[0], 1.0 - uv[1]));
+                        *uv = [fbx_uv.x, 1.0 - fbx_uv.y];
+                    }
+                    material_mesh.remove_attribute(Mesh::ATTRIBUTE_TANGENT);
+                }
+                // Tangents can't be generated without UVs.
+                let has_uvs = material_mesh.contains_attribute(Mesh::ATTRIBUTE_UV_0);
+                if has_uvs && !material_mesh.contains_attribute(Mesh::ATTRIBUTE_TANGENT) {
+                    material_mesh
+                        .generate_tangents()
+                        .context("Failed to generate tangents")?;
+                }
 
                 let label = format!("{label}{i}");
 
@@ -400,10 +1014,14 @@ impl<'b, 'w> Loader<'b, 'w> {
                     .load_context
                     .set_labeled_asset(&label, LoadedAsset::new(material_mesh));
                 self.scene.bevy_meshes.insert(handle.clone(), label);
-                handle
+                Ok(handle)
             })
-            .collect();
-        Ok(all_handles)
+            .collect::<anyhow::Result<_>>()?;
+        Ok(LoadedGeometry {
+            bevy_mesh_handles: all_handles,
+            morph_targets,
+            uv_sets: uv_set_names,
+        })
     }
 
     // Note: FBX meshes can have multiple different materials, it's not just a mesh.
@@ -420,29 +1038,64 @@ impl<'b, 'w> Loader<'b, 'w> {
         debug!("Loading FBX mesh: {label}");
 
         let bevy_obj = mesh_obj.geometry().context("Failed to get geometry")?;
+        let geometric_transform = fbx_transform::geometric_transform(*mesh_obj)
+            .context("Failed to read geometric transform")?;
 
         // async and iterators into for are necessary because of `async` `read_asset_bytes`
         // call in `load_video_clip`  that virally infect everything.
         // This can't even be ran in parallel, because we store already-encountered materials.
         let mut materials = Vec::new();
+        let mut material_uv_sets = Vec::new();
+        let mut material_uv_transforms = Vec::new();
         for mat in mesh_obj.materials() {
-            let mat = self.load_material(mat).await;
-            let mat = mat.context("Failed to load materials for mesh")?;
-            materials.push(mat);
+            material_uv_sets.push(mat.texture_uv_set());
+            let handle = self.load_material(mat).await;
+            let handle = handle.context("Failed to load materials for mesh");
+            let handle = match self.recover(mat.object_id(), mat.name(), handle)? {
+                Some(handle) => handle,
+                None => self.load_fallback_material(mat).await?,
+            };
+            material_uv_transforms.push(self.uv_transforms.get(&handle).copied());
+            materials.push(handle);
         }
-        let material_count = materials.len();
-        if material_count == 0 {
+        if materials.is_empty() {
             materials.push(Handle::default());
         }
 
-        let bevy_mesh_handles = self
-            .load_bevy_mesh(bevy_obj, material_count)
-            .context("Failed to load geometry mesh")?;
+        let (skin, skin_weights) = match bevy_obj.skins().next() {
+            Some(skin_obj) => {
+                let (skin, weights) = self
+                    .load_skin(mesh_obj, bevy_obj, skin_obj, geometric_transform, &label)
+                    .context("Failed to load skin")?;
+                (Some(skin), Some(weights))
+            }
+            None => (None, None),
+        };
+
+        let morph_shapes = load_blend_shapes(bevy_obj, &mut self.scene.diagnostics)
+            .context("Failed to load blend shapes")?;
+
+        let geometry = self
+            .load_bevy_mesh(
+                bevy_obj,
+                skin_weights.as_ref(),
+                &morph_shapes,
+                &material_uv_sets,
+                &material_uv_transforms,
+            )
+            .map_err(|source| FbxError::InvalidGeometry {
+                mesh: label.clone(),
+                source,
+            })?;
 
         let mesh = FbxMesh {
             name: mesh_obj.name().map(Into::into),
-            bevy_mesh_handles,
+            bevy_mesh_handles: geometry.bevy_mesh_handles,
             materials,
+            skin,
+            morph_targets: geometry.morph_targets,
+            transform: Transform::from_matrix(self.scale_space(geometric_transform)),
+            uv_sets: geometry.uv_sets,
         };
 
         let mesh_handle = self
@@ -454,35 +1107,230 @@ impl<'b, 'w> Loader<'b, 'w> {
         Ok(mesh)
     }
 
+    /// Read the `Cluster`s of a `Skin` deformer.
+    ///
+    /// Each cluster is a joint: it links a bone node to the control points it influences.
+    fn load_skin(
+        &mut self,
+        mesh_obj: object::model::MeshHandle,
+        geometry_obj: object::geometry::MeshHandle,
+        skin_obj: SkinHandle,
+        geometric_transform: Mat4,
+        label: &str,
+    ) -> anyhow::Result<(FbxSkin, SkinWeights)> {
+        let control_point_count = geometry_obj
+            .polygon_vertices()
+            .context("Failed to get polygon vertices")?
+            .raw_control_points()?
+            .count();
+        let mut influences = vec![Vec::new(); control_point_count];
+        let mut joints = Vec::new();
+        let mut inverse_bindposes = Vec::new();
+        for cluster in skin_obj.clusters() {
+            let Some(bone) = cluster.bone() else {
+                FbxDiagnostic::warning("Skin cluster without bone, skipping it")
+                    .with_object(cluster.object_id(), cluster.name())
+                    .report(&mut self.scene.diagnostics);
+                continue;
+            };
+            let joint_index = joints.len() as u16;
+            // Bevy computes the vertex position as `joint_global * inverse_bindpose * vertex`,
+            // with vertex in geometry space. At bind time, `joint_global` is `TransformLink`,
+            // and the mesh global transform is `Transform`, the geometric transform
+            // moves the vertex from geometry to mesh space.
+            let mesh_bind = cluster.transform().unwrap_or_default();
+            let bone_bind = cluster.transform_link().unwrap_or_default();
+            joints.push(bone.object_id());
+            let inverse_bindpose = bone_bind.inverse() * mesh_bind * geometric_transform;
+            inverse_bindposes.push(self.scale_space(inverse_bindpose));
+
+            for (&cpi, &weight) in cluster.indexes().iter().zip(cluster.weights()) {
+                match influences.get_mut(cpi as usize) {
+                    Some(cp_influences) => cp_influences.push((joint_index, weight as f32)),
+                    None => bail!("Cluster control point index out of range: {cpi}"),
+                }
+            }
+        }
+        // Vertices not fully influenced by the clusters are attached to the mesh node
+        // itself, so that they still follow the mesh rather than collapse to the origin.
+        let mesh_joint_index = joints.len() as u16;
+        let mut uses_mesh_joint = false;
+        let mut weights = SkinWeights {
+            joint_indices: Vec::with_capacity(control_point_count),
+            joint_weights: Vec::with_capacity(control_point_count),
+        };
+        for mut cp_influences in influences {
+            cp_influences.sort_by(|(_, l), (_, r)| r.total_cmp(l));
+            cp_influences.truncate(MAX_VERTEX_INFLUENCES);
+            let total: f32 = cp_influences.iter().map(|(_, weight)| weight).sum();
+            if total < 0.999 && cp_influences.len() < MAX_VERTEX_INFLUENCES {
+                cp_influences.push((mesh_joint_index, 1.0 - total));
+                uses_mesh_joint = true;
+            } else if total <= 0.0 {
+                // All the influences have a zero weight, normalizing them would give NaN.
+                cp_influences.clear();
+                cp_influences.push((mesh_joint_index, 1.0));
+                uses_mesh_joint = true;
+            } else {
+                cp_influences
+                    .iter_mut()
+                    .for_each(|(_, weight)| *weight /= total);
+            }
+            let mut joint_indices = [0; MAX_VERTEX_INFLUENCES];
+            let mut joint_weights = [0.0; MAX_VERTEX_INFLUENCES];
+            for (i, (joint, weight)) in cp_influences.into_iter().enumerate() {
+                joint_indices[i] = joint;
+                joint_weights[i] = weight;
+            }
+            weights.joint_indices.push(joint_indices);
+            weights.joint_weights.push(joint_weights);
+        }
+        if uses_mesh_joint {
+            joints.push(mesh_obj.object_id());
+            inverse_bindposes.push(self.scale_space(geometric_transform));
+        }
+        debug!("Skin for {label} has {} joints", joints.len());
+
+        let inverse_bindposes = SkinnedMeshInverseBindposes::from(inverse_bindposes);
+        let inverse_bindposes = self.load_context.set_labeled_asset(
+            &format!("{label}/InverseBindposes"),
+            LoadedAsset::new(inverse_bindposes),
+        );
+        let skin = FbxSkin {
+            joints,
+            inverse_bindposes,
+        };
+        Ok((skin, weights))
+    }
+
+    fn texture_file_names<'a>(
+        &'a self,
+        video_clip_obj: object::video::ClipHandle<'a>,
+    ) -> TextureFileNames<'a> {
+        let relative_filename = video_clip_obj.relative_filename().ok();
+        let filename = video_clip_obj
+            .node()
+            .first_child_by_name("Filename")
+            .and_then(|node| node.attributes().first()?.get_string());
+        TextureFileNames {
+            fbx_path: self.load_context.path(),
+            relative_filename: relative_filename.filter(|name| !name.is_empty()),
+            filename: filename.filter(|name| !name.is_empty()),
+        }
+    }
+
+    /// Where the image of `texture_obj` comes from, resolving its file path
+    /// with the [`TextureResolver`] if it isn't embedded.
+    fn image_key(&self, texture_obj: TextureHandle<'_>) -> ImageKey {
+        let image = texture_obj.video_clip().and_then(|video_clip_obj| {
+            if let Some(content) = video_clip_obj.content() {
+                let mut hasher = DefaultHasher::new();
+                content.hash(&mut hasher);
+                return Some(ImageKey::Content(hasher.finish()));
+            }
+            let names = self.texture_file_names(video_clip_obj);
+            let paths = self
+                .texture_resolver
+                .resolve(&names, self.load_context.asset_io());
+            (!paths.is_empty()).then_some(ImageKey::Paths(paths))
+        });
+        image.unwrap_or(ImageKey::Object(texture_obj.object_id()))
+    }
+
+    /// The image of `texture_obj`, shared with the textures with the same [`TextureKey`].
+    async fn load_texture_image(
+        &mut self,
+        texture_obj: TextureHandle<'_>,
+        is_srgb: bool,
+    ) -> anyhow::Result<Handle<Image>> {
+        let key = TextureKey {
+            image: self.image_key(texture_obj),
+            is_srgb,
+            address_modes: address_modes(texture_obj).ok(),
+        };
+        if let Some(handle) = self.texture_images.get(&key) {
+            debug!(
+                "Already encountered texture image: {:?}, skipping",
+                key.image
+            );
+            return Ok(handle.clone());
+        }
+        // Fallback to the object id when the name is already used by another image.
+        let suffix = if is_srgb { "" } else { "/linear" };
+        let named = texture_obj
+            .name()
+            .filter(|name| !name.is_empty())
+            .map(|name| format!("FbxTexture@{name}{suffix}"));
+        let label = match named {
+            Some(named) if !self.scene.textures.contains_key(&named) => named,
+            _ => format!("FbxTexture{}{suffix}", texture_obj.object_id().raw()),
+        };
+
+        let handle = match &key.image {
+            // Several paths means the resolver couldn't check which one exists,
+            // only the loader can find out by reading them.
+            ImageKey::Paths(paths)
+                if paths.len() == 1 && is_srgb && self.settings.external_textures_as_assets =>
+            {
+                debug!("Loading texture {label} with the AssetServer: {paths:?}");
+                let path = AssetPath::new(paths[0].clone(), None);
+                self.texture_dependencies.push(path.clone());
+                self.load_context.get_handle(path)
+            }
+            _ => {
+                let image = self
+                    .get_texture_or_placeholder(texture_obj, &key.image, is_srgb)
+                    .await?;
+                self.load_context
+                    .set_labeled_asset(&label, LoadedAsset::new(image))
+            }
+        };
+        self.scene.textures.insert(label, handle.clone());
+        self.texture_images.insert(key, handle.clone());
+        Ok(handle)
+    }
+
     async fn load_video_clip(
         &mut self,
         video_clip_obj: object::video::ClipHandle<'_>,
+        image: &ImageKey,
+        is_srgb: bool,
     ) -> anyhow::Result<Image> {
         debug!("Loading texture image: {:?}", video_clip_obj.name());
 
-        let relative_filename = video_clip_obj
-            .relative_filename()
-            .context("Failed to get relative filename of texture image")?;
-        debug!("Relative filename: {:?}", relative_filename);
+        let names = self.texture_file_names(video_clip_obj);
+        debug!("Texture file names: {names:?}");
+        let file_name = names
+            .relative_filename
+            .or(names.filename)
+            .context("No file name for texture image")?;
 
-        let file_ext = Path::new(&relative_filename)
-            .extension()
-            .unwrap()
-            .to_str()
-            .unwrap()
-            .to_ascii_lowercase();
-        let image: Vec<u8> = if let Some(content) = video_clip_obj.content() {
+        let (image, image_path): (Vec<u8>, PathBuf) = match (video_clip_obj.content(), image) {
             // TODO: the clone here is absolutely unnecessary, but there
             // is no way to reconciliate its lifetime with the other branch of
-            // this if/else
-            content.to_vec()
-        } else {
-            let parent = self.load_context.path().parent().unwrap();
-            let clean_relative_filename = relative_filename.replace('\\', "/");
-            let image_path = parent.join(clean_relative_filename);
-            self.load_context.read_asset_bytes(image_path).await?
+            // this match
+            (Some(content), _) => (content.to_vec(), file_name.replace('\\', "/").into()),
+            (None, ImageKey::Paths(image_paths)) => {
+                debug!("Resolved texture image paths: {image_paths:?}");
+                let mut image = None;
+                for image_path in image_paths {
+                    match self.load_context.read_asset_bytes(image_path).await {
+                        Ok(bytes) => {
+                            image = Some((bytes, image_path.clone()));
+                            break;
+                        }
+                        Err(err) => debug!("Failed to read texture image {image_path:?}: {err}"),
+                    }
+                }
+                image.with_context(|| format!("Texture image {file_name} not found"))?
+            }
+            (None, _) => bail!("Texture image {file_name} not found"),
         };
-        let is_srgb = false; // TODO
+        let file_ext = image_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .with_context(|| format!("No file extension for texture image {file_name}"))?
+            .to_ascii_lowercase();
         let image = Image::from_buffer(
             &image,
             ImageType::Extension(&file_ext),
@@ -504,8 +1352,10 @@ impl<'b, 'w> Loader<'b, 'w> {
         MaterialLoader {
             static_load,
             dynamic_load,
+            color_textures,
             preprocess_textures,
             with_textures,
+            ..
         }: MaterialLoader,
     ) -> anyhow::Result<Option<StandardMaterial>> {
         use crate::utils::fbx_extend::*;
@@ -513,13 +1363,17 @@ impl<'b, 'w> Loader<'b, 'w> {
             Processed(Image),
             Handle(TextureHandle<'a>),
         }
+        let is_srgb = |label: &str| color_textures.contains(&label);
         let mut textures = HashMap::default();
         // code is a bit tricky so here is a rundown:
         // 1. Load all textures that are meant to be preprocessed by the
         //    MaterialLoader
         for &label in dynamic_load {
             if let Some(texture) = material_obj.load_texture(label) {
-                let texture = self.get_texture(texture).await?;
+                let image = self.image_key(texture);
+                let texture = self
+                    .get_texture_or_placeholder(texture, &image, is_srgb(label))
+                    .await?;
                 textures.insert(label, texture);
             }
         }
@@ -534,71 +1388,58 @@ impl<'b, 'w> Loader<'b, 'w> {
                     .load_texture(l)
                     .map(|te| (*l, TextureSource::Handle(te)))
             }));
-        // 3. For each of those, create an image handle (with potential caching based on the texture name)
+        // 3. For each of those, create an image handle (textures are cached
+        //    by image file and sampler, see `TextureKey`)
         for (label, texture) in texture_handles_iter {
-            let handle_label = match texture {
-                TextureSource::Handle(texture_handle) => match texture_handle.name() {
-                    Some(name) if !name.is_empty() => format!("FbxTexture@{name}"),
-                    _ => format!("FbxTexture{}", texture_handle.object_id().raw()),
-                },
-                TextureSource::Processed(_) => match material_obj.name() {
-                    Some(name) if !name.is_empty() => format!("FbxTextureMat@{name}/{label}"),
-                    _ => format!("FbxTextureMat{}/{label}", material_obj.object_id().raw()),
-                },
-            };
-
-            // Either copy the already-created handle or create a new asset
-            // for each image or texture to load.
-            let handle = if let Some(handle) = self.scene.textures.get(&handle_label) {
-                debug!("Already encountered texture: {label}, skipping");
-
-                handle.clone()
-            } else {
-                let texture = match texture {
-                    TextureSource::Processed(texture) => texture,
-                    TextureSource::Handle(texture) => self.get_texture(texture).await?,
-                };
-                let handle = self
-                    .load_context
-                    .set_labeled_asset(&handle_label, LoadedAsset::new(texture));
-                self.scene.textures.insert(handle_label, handle.clone());
-                handle
+            let handle = match texture {
+                TextureSource::Handle(texture) => {
+                    self.load_texture_image(texture, is_srgb(label)).await?
+                }
+                TextureSource::Processed(texture) => {
+                    let named = material_obj
+                        .name()
+                        .filter(|name| !name.is_empty())
+                        .map(|name| format!("FbxTextureMat@{name}/{label}"));
+                    let handle_label = match named {
+                        Some(named) if !self.scene.textures.contains_key(&named) => named,
+                        _ => format!("FbxTextureMat{}/{label}", material_obj.object_id().raw()),
+                    };
+                    let handle = self
+                        .load_context
+                        .set_labeled_asset(&handle_label, LoadedAsset::new(texture));
+                    self.scene.textures.insert(handle_label, handle.clone());
+                    handle
+                }
             };
             texture_handles.insert(label, handle);
         }
-        // 4. Call with all the texture handles
-        Ok(with_textures(material_obj, texture_handles))
+        // 4. Call with all the texture handles and transforms
+        let mut transforms = HashMap::default();
+        for &label in dynamic_load.iter().chain(static_load) {
+            if let Some(texture) = material_obj.load_texture(label) {
+                transforms.insert(label, texture_transform(texture)?);
+            }
+        }
+        Ok(with_textures(material_obj, texture_handles, transforms))
     }
 
     async fn get_texture(
         &mut self,
         texture_obj: object::texture::TextureHandle<'_>,
+        image: &ImageKey,
+        is_srgb: bool,
     ) -> anyhow::Result<Image> {
-        let properties = texture_obj.properties();
-        let address_mode_u = {
-            let val = properties
-                .wrap_mode_u_or_default()
-                .context("Failed to load wrap mode for U axis")?;
-            match val {
-                WrapMode::Repeat => AddressMode::Repeat,
-                WrapMode::Clamp => AddressMode::ClampToEdge,
-            }
-        };
-        let address_mode_v = {
-            let val = properties
-                .wrap_mode_v_or_default()
-                .context("Failed to load wrap mode for V axis")?;
-            match val {
-                WrapMode::Repeat => AddressMode::Repeat,
-                WrapMode::Clamp => AddressMode::ClampToEdge,
-            }
-        };
+        let [address_mode_u, address_mode_v] = address_modes(texture_obj)?;
         let video_clip_obj = texture_obj
             .video_clip()
             .context("No image data for texture object")?;
 
-        let image: Result<Image, anyhow::Error> = self.load_video_clip(video_clip_obj).await;
-        let mut image = image.context("Failed to load texture image")?;
+        let image: Result<Image, anyhow::Error> =
+            self.load_video_clip(video_clip_obj, image, is_srgb).await;
+        let mut image = image.map_err(|source| FbxError::Texture {
+            name: texture_obj.name().unwrap_or_default().to_owned(),
+            source,
+        })?;
 
         image.sampler_descriptor = ImageSampler::Descriptor(SamplerDescriptor {
             address_mode_u,
@@ -608,6 +1449,43 @@ impl<'b, 'w> Loader<'b, 'w> {
         Ok(image)
     }
 
+    /// [`Self::get_texture`], or a placeholder texture in [lenient] mode.
+    ///
+    /// [lenient]: FbxLoaderSettings::lenient
+    async fn get_texture_or_placeholder(
+        &mut self,
+        texture_obj: object::texture::TextureHandle<'_>,
+        image: &ImageKey,
+        is_srgb: bool,
+    ) -> anyhow::Result<Image> {
+        let texture = self.get_texture(texture_obj, image, is_srgb).await;
+        let texture = self.recover(texture_obj.object_id(), texture_obj.name(), texture)?;
+        Ok(texture.unwrap_or_else(placeholder_texture))
+    }
+
+    /// Load `material_obj` with [`LOAD_FALLBACK`], used in [lenient] mode
+    /// when the material loaders fail to load it.
+    ///
+    /// [`LOAD_FALLBACK`]: crate::material_loader::LOAD_FALLBACK
+    /// [lenient]: FbxLoaderSettings::lenient
+    async fn load_fallback_material(
+        &mut self,
+        material_obj: object::material::MaterialHandle<'_>,
+    ) -> anyhow::Result<Handle<StandardMaterial>> {
+        let label = match material_obj.name() {
+            Some(name) if !name.is_empty() => format!("FbxMaterial@{name}"),
+            _ => format!("FbxMaterial{}", material_obj.object_id().raw()),
+        };
+        let material = self.run_loader(material_obj, LOAD_FALLBACK).await;
+        let material = self.recover(material_obj.object_id(), material_obj.name(), material)?;
+        let material = material.flatten().unwrap_or_default();
+        let handle = self
+            .load_context
+            .set_labeled_asset(&label, LoadedAsset::new(material));
+        self.scene.materials.insert(label, handle.clone());
+        Ok(handle)
+    }
+
     async fn load_material(
         &mut self,
         material_obj: object::material::MaterialHandle<'_>,
@@ -624,53 +1502,271 @@ impl<'b, 'w> Loader<'b, 'w> {
         debug!("Loading FBX material: {label}");
 
         let mut material = None;
+        let mut baked_uv_transform: &[&str] = &[];
         let loaders = self.material_loaders.clone();
-        for &loader in &loaders {
+        for (i, &loader) in loaders.iter().enumerate() {
             if let Some(loader_material) = self.run_loader(material_obj, loader).await? {
+                // The last loader is usually a catch-all for shading models
+                // the other loaders don't support.
+                if i != 0 && i == loaders.len() - 1 {
+                    let shading_model = material_obj
+                        .node()
+                        .first_child_by_name("ShadingModel")
+                        .and_then(|node| node.attributes().first()?.get_string());
+                    let message = format!(
+                        "Unsupported shading model {shading_model:?}, using a fallback material"
+                    );
+                    FbxDiagnostic::warning(message)
+                        .with_object(material_obj.object_id(), material_obj.name())
+                        .report(&mut self.scene.diagnostics);
+                }
                 material = Some(loader_material);
+                baked_uv_transform = loader.baked_uv_transform;
                 break;
             }
         }
         let material = material.context("None of the material loaders could load this material")?;
+        let uv_transform = baked_uv_transform
+            .iter()
+            .find_map(|label| material_obj.load_texture(label))
+            .map(texture_transform)
+            .transpose()?;
         let handle = self
             .load_context
             .set_labeled_asset(&label, LoadedAsset::new(material));
         debug!("Successfully loaded material: {label}");
 
+        if let Some(uv_transform) = uv_transform.filter(|t| !t.is_identity()) {
+            self.uv_transforms.insert(handle.clone_weak(), uv_transform);
+        }
         self.scene.materials.insert(label, handle.clone());
         Ok(handle)
     }
 }
 
-fn traverse_hierarchy(node: ModelHandle, hierarchy: &mut HashMap<ObjectId, FbxObject>) {
+/// The sampler address modes of the U and V axis of `texture_obj`.
+fn address_modes(texture_obj: TextureHandle<'_>) -> anyhow::Result<[AddressMode; 2]> {
+    let address_mode = |wrap_mode| match wrap_mode {
+        WrapMode::Repeat => AddressMode::Repeat,
+        WrapMode::Clamp => AddressMode::ClampToEdge,
+    };
+    let properties = texture_obj.properties();
+    let u = properties
+        .wrap_mode_u_or_default()
+        .context("Failed to load wrap mode for U axis")?;
+    let v = properties
+        .wrap_mode_v_or_default()
+        .context("Failed to load wrap mode for V axis")?;
+    Ok([address_mode(u), address_mode(v)])
+}
+
+/// Read the UV transform properties of `texture_obj`.
+fn texture_transform(texture_obj: TextureHandle<'_>) -> anyhow::Result<FbxTextureTransform> {
+    let properties = texture_obj.properties();
+    let run = || -> anyhow::Result<_> {
+        let vec2 = |vec: DVec3| vec.truncate().as_vec2();
+        Ok(FbxTextureTransform {
+            translation: vec2(properties.translation_or_default()?.into()),
+            rotation: (properties.rotation_or_default()?[2] as f32).to_radians(),
+            scale: vec2(properties.scaling_or_default()?.into()),
+            rotation_pivot: vec2(properties.rotation_pivot_or_default()?.into()),
+            scaling_pivot: vec2(properties.scaling_pivot_or_default()?.into()),
+            uv_swap: properties.uv_swap_or_default()?,
+        })
+    };
+    run().context("Failed to load texture UV transform")
+}
+
+/// A magenta and black checker texture, replacing textures
+/// that failed to load in lenient mode.
+fn placeholder_texture() -> Image {
+    const SIZE: u32 = 8;
+    const MAGENTA: [u8; 4] = [255, 0, 255, 255];
+    const BLACK: [u8; 4] = [0, 0, 0, 255];
+    let data = (0..SIZE * SIZE)
+        .flat_map(|i| match (i % SIZE + i / SIZE) % 2 {
+            0 => MAGENTA,
+            _ => BLACK,
+        })
+        .collect();
+    let size = Extent3d {
+        width: SIZE,
+        height: SIZE,
+        depth_or_array_layers: 1,
+    };
+    let mut image = Image::new(
+        size,
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    image.sampler_descriptor = ImageSampler::nearest();
+    image
+}
+
+/// Only keep the deltas of the vertices at the `kept` indices.
+fn select_deltas(deltas: &FbxMorphDeltas, kept: &[usize]) -> FbxMorphDeltas {
+    let select = |deltas: &[[f32; 3]]| kept.iter().map(|&i| deltas[i]).collect();
+    FbxMorphDeltas {
+        positions: select(&deltas.positions),
+        normals: select(&deltas.normals),
+    }
+}
+
+/// Read the tangents authored in the `LayerElementTangent` and
+/// `LayerElementBinormal` of a geometry, for each triangle corner.
+///
+/// `None` if the geometry doesn't have both tangents and binormals.
+fn load_tangents(
+    geometry_obj: object::geometry::MeshHandle,
+    triangles: &TriangleVertices,
+    normals: &[[f32; 3]],
+) -> anyhow::Result<Option<Vec<[f32; 4]>>> {
+    let tangents = RawLayerElement::find(&geometry_obj, "LayerElementTangent", "Tangents")?;
+    let binormals = RawLayerElement::find(&geometry_obj, "LayerElementBinormal", "Binormals")?;
+    let (Some(tangents), Some(binormals)) = (tangents, binormals) else {
+        return Ok(None);
+    };
+    let corners = TriangleCorners::new(triangles)?;
+    let tangents = tangents
+        .vec3s(&corners)
+        .context("Failed to read tangents")?;
+    let binormals = binormals
+        .vec3s(&corners)
+        .context("Failed to read binormals")?;
+    // The FBX binormal points toward increasing V, but we flip the V coordinate
+    // of UVs, so the bevy bitangent is the opposite of the FBX binormal.
+    let get_tangent = |((normal, tangent), binormal): ((&[f32; 3], [f32; 3]), [f32; 3])| {
+        let (normal, tangent) = (Vec3::from(*normal), Vec3::from(tangent));
+        let flipped = normal.cross(tangent).dot(Vec3::from(binormal)) >= 0.0;
+        let handedness = if flipped { -1.0 } else { 1.0 };
+        tangent.extend(handedness).into()
+    };
+    let tangents = normals.iter().zip(tangents).zip(binormals);
+    Ok(Some(tangents.map(get_tangent).collect()))
+}
+
+/// Read the `BlendShape` deformers of a geometry.
+///
+/// Each `BlendShapeChannel` is a morph target. A channel may have several
+/// `Shape`s, to progressively deform the mesh with "in-between" targets,
+/// we only keep the one fully applied at 100%.
+fn load_blend_shapes(
+    geometry_obj: object::geometry::MeshHandle,
+    diagnostics: &mut Vec<FbxDiagnostic>,
+) -> anyhow::Result<Vec<MorphShape>> {
+    let control_point_count = geometry_obj
+        .polygon_vertices()
+        .context("Failed to get polygon vertices")?
+        .raw_control_points()?
+        .count();
+    let mut morph_shapes = Vec::new();
+    let channels = geometry_obj
+        .blendshapes()
+        .flat_map(|blend_shape| blend_shape.blendshape_channels());
+    for channel in channels {
+        let name = match channel.name() {
+            Some(name) if !name.is_empty() => name.to_owned(),
+            _ => format!("FbxBlendShapeChannel{}", channel.object_id().raw()),
+        };
+        let shapes: Vec<_> = channel.shapes().collect();
+        if shapes.len() > 1 {
+            let message = format!(
+                "Blend shape channel has {} in-between shapes, only keeping the last one",
+                shapes.len() - 1,
+            );
+            FbxDiagnostic::warning(message)
+                .with_object(channel.object_id(), Some(&name))
+                .report(diagnostics);
+        }
+        // Shapes are sorted by increasing `FullWeights`, the last one is the full deformation.
+        let Some(shape) = shapes.last() else {
+            FbxDiagnostic::warning("Blend shape channel without shape, skipping it")
+                .with_object(channel.object_id(), Some(&name))
+                .report(diagnostics);
+            continue;
+        };
+        let mut positions = vec![Vec3::ZERO; control_point_count];
+        let mut normals = vec![Vec3::ZERO; control_point_count];
+        let vertices = shape.vertices().chunks_exact(3);
+        for (&cpi, delta) in shape.indexes().iter().zip(vertices) {
+            let Some(position) = positions.get_mut(cpi as usize) else {
+                bail!("Shape control point index out of range: {cpi}");
+            };
+            *position = DVec3::from_slice(delta).as_vec3();
+        }
+        let shape_normals = shape.normals().chunks_exact(3);
+        for (&cpi, delta) in shape.indexes().iter().zip(shape_normals) {
+            if let Some(normal) = normals.get_mut(cpi as usize) {
+                *normal = DVec3::from_slice(delta).as_vec3();
+            }
+        }
+        morph_shapes.push(MorphShape {
+            name,
+            default_weight: (channel.deform_percent() / 100.0) as f32,
+            positions,
+            normals,
+        });
+    }
+    Ok(morph_shapes)
+}
+
+fn traverse_hierarchy(
+    node: ModelHandle,
+    kept_nodes: &HashSet<ObjectId>,
+    lenient: bool,
+    hierarchy: &mut HashMap<ObjectId, FbxObject>,
+    diagnostics: &mut Vec<FbxDiagnostic>,
+) -> anyhow::Result<()> {
     #[cfg(feature = "profile")]
     let _hierarchy_span = info_span!("traverse_fbx_hierarchy").entered();
 
-    traverse_hierarchy_rec(node, None, hierarchy);
+    traverse_hierarchy_rec(node, None, kept_nodes, lenient, hierarchy, diagnostics)?;
     debug!("Tree has {} nodes", hierarchy.len());
     trace!("root: {:?}", node.object_node_id());
+    Ok(())
 }
 fn traverse_hierarchy_rec(
     node: ModelHandle,
     parent: Option<FbxTransform>,
+    kept_nodes: &HashSet<ObjectId>,
+    lenient: bool,
     hierarchy: &mut HashMap<ObjectId, FbxObject>,
-) -> bool {
+    diagnostics: &mut Vec<FbxDiagnostic>,
+) -> anyhow::Result<bool> {
     let name = node.name().map(|s| s.to_owned());
-    let data = FbxTransform::from_node(node, parent);
+    let data = match FbxTransform::from_node(node, parent) {
+        Ok(data) => data,
+        Err(err) if lenient => {
+            let message = format!("Failed to read transform: {err:#}, using identity instead");
+            FbxDiagnostic::error(message)
+                .with_object(node.object_id(), node.name())
+                .report(diagnostics);
+            FbxTransform::identity(parent)
+        }
+        Err(err) => return Err(err.context(format!("Failed to read transform of node {name:?}"))),
+    };
 
     let mut mesh_leaf = false;
-    node.child_models().for_each(|child| {
-        mesh_leaf |= traverse_hierarchy_rec(*child, Some(data), hierarchy);
-    });
-    if node.subclass() == "Mesh" {
+    for child in node.child_models() {
+        let child = traverse_hierarchy_rec(
+            *child,
+            Some(data),
+            kept_nodes,
+            lenient,
+            hierarchy,
+            diagnostics,
+        );
+        mesh_leaf |= child?;
+    }
+    if node.subclass() == "Mesh" || kept_nodes.contains(&node.object_id()) {
         mesh_leaf = true;
     }
-    // Only keep nodes that have Mesh children
-    // (ie defines something visible in the scene)
+    // Only keep nodes that have Mesh, bone, camera or light children
+    // (ie defines something visible in the scene, used for skinning, or lighting the scene)
     // I've found some very unwindy FBX files with several thousand
     // nodes that served no practical purposes,
-    // This also trims deformers and limb nodes, which we currently
-    // do not support
+    // This also trims limb nodes not used by any skin, and cameras when not requested.
     if mesh_leaf {
         let fbx_object = FbxObject {
             name,
@@ -678,6 +1774,10 @@ fn traverse_hierarchy_rec(
             children: node.child_models().map(|c| c.object_id()).collect(),
         };
         hierarchy.insert(node.object_id(), fbx_object);
+    } else {
+        FbxDiagnostic::info("Node without mesh, joint, camera or light, skipping it")
+            .with_object(node.object_id(), node.name())
+            .report(diagnostics);
     }
-    mesh_leaf
+    Ok(mesh_leaf)
 }
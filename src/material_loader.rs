@@ -9,6 +9,13 @@ use bevy::{
 use fbxcel_dom::v7400::{data::material::ShadingModel, object::material::MaterialHandle};
 use rgb::RGB;
 
+use crate::FbxTextureTransform;
+
+/// The textures of a material, by FBX texture field name.
+pub type TextureHandles = HashMap<&'static str, Handle<Image>>;
+/// The [`FbxTextureTransform`] of the textures of a material, by FBX texture field name.
+pub type TextureTransforms = HashMap<&'static str, FbxTextureTransform>;
+
 /// Load materials from an FBX file.
 ///
 /// Define your own to extend `bevy_mod_fbx`'s material loading capabilities.
@@ -37,6 +44,21 @@ pub struct MaterialLoader {
     /// [`FbxLoader`]: crate::FbxLoader
     pub dynamic_load: &'static [&'static str],
 
+    /// The FBX texture field names, among `static_load` and `dynamic_load`,
+    /// holding color data, such as base color or emissive maps.
+    ///
+    /// Those textures are loaded in the sRGB color space, the others,
+    /// such as normal, roughness or metallic maps, are loaded as linear data.
+    /// If a same image is used both as color and as data, it is loaded twice.
+    pub color_textures: &'static [&'static str],
+
+    /// The FBX texture field names, by order of preference, whose
+    /// [`FbxTextureTransform`] is baked into the UVs of the meshes using the material.
+    ///
+    /// `StandardMaterial` has no UV transform, and all its textures share the same UVs,
+    /// so only the transform of the first of those textures the material has is applied.
+    pub baked_uv_transform: &'static [&'static str],
+
     /// Run some math on the loaded textures, handy if you have to convert between texture
     /// formats or swap color channels.
     ///
@@ -49,8 +71,11 @@ pub struct MaterialLoader {
 
     /// Create and return the bevy [`StandardMaterial`] based on the [`Handle<Image>`] loaded
     /// from the return value of `preprocess_textures`.
+    ///
+    /// The third argument is the [`FbxTextureTransform`] of the textures declared
+    /// in `static_load` and `dynamic_load`.
     pub with_textures:
-        fn(MaterialHandle, HashMap<&'static str, Handle<Image>>) -> Option<StandardMaterial>,
+        fn(MaterialHandle, TextureHandles, TextureTransforms) -> Option<StandardMaterial>,
 }
 
 const SPECULAR_TO_METALLIC_RATIO: f32 = 0.8;
@@ -68,8 +93,15 @@ pub const LOAD_LAMBERT_PHONG: MaterialLoader = MaterialLoader {
         "TransparentColor",
     ],
     dynamic_load: &[],
+    color_textures: &["EmissiveColor", "DiffuseColor", "TransparentColor"],
+    baked_uv_transform: &[
+        "DiffuseColor",
+        "TransparentColor",
+        "EmissiveColor",
+        "NormalMap",
+    ],
     preprocess_textures: |_, _| {},
-    with_textures: |material_obj, textures| {
+    with_textures: |material_obj, textures, _| {
         use AlphaMode::{Blend, Opaque};
         use ShadingModel::{Lambert, Phong};
         let properties = material_obj.properties();
@@ -117,8 +149,10 @@ pub const LOAD_LAMBERT_PHONG: MaterialLoader = MaterialLoader {
 pub const LOAD_FALLBACK: MaterialLoader = MaterialLoader {
     static_load: &[],
     dynamic_load: &[],
+    color_textures: &[],
+    baked_uv_transform: &[],
     preprocess_textures: |_, _| {},
-    with_textures: |material_obj, _| {
+    with_textures: |material_obj, _, _| {
         let properties = material_obj.properties();
         let base_color = properties
             .diffuse_color()
@@ -171,13 +205,22 @@ pub const LOAD_MAYA_PBR: MaterialLoader = MaterialLoader {
         "Maya|TEX_emissive_map",
     ],
     dynamic_load: &["Maya|TEX_metallic_map", "Maya|TEX_roughness_map"],
+    color_textures: &["Maya|TEX_color_map", "Maya|TEX_emissive_map"],
+    baked_uv_transform: &[
+        "Maya|TEX_color_map",
+        "Maya|TEX_normal_map",
+        "Maya|TEX_emissive_map",
+        "Maya|TEX_ao_map",
+        "Maya|TEX_metallic_map",
+        "Maya|TEX_roughness_map",
+    ],
     // FIXME: this assumes both metallic map and roughness map
     // are encoded in texture formats that can be stored as
     // a byte array in CPU memory.
     // This is not the case for compressed formats such as KTX or DDS
     // FIXME: this also assumes the texture channels are 8 bit.
     preprocess_textures: |material_handle, images| {
-        use bevy::render::render_resource::{TextureDimension::D2, TextureFormat::Rgba8UnormSrgb};
+        use bevy::render::render_resource::{TextureDimension::D2, TextureFormat::Rgba8Unorm};
         let mut run = || {
             // return early if we detect this material is not Maya's PBR material
             let mat_maya_type = material_handle.get_i32("Maya|TypeId")?;
@@ -210,13 +253,13 @@ pub const LOAD_MAYA_PBR: MaterialLoader = MaterialLoader {
                     [0, combine_colors(rough), combine_colors(metallic), 255]
                 })
                 .collect();
-            let metallic_rough = Image::new(image_size, D2, metallic_rough, Rgba8UnormSrgb);
+            let metallic_rough = Image::new(image_size, D2, metallic_rough, Rgba8Unorm);
             images.insert("Metallic_Roughness", metallic_rough);
             Some(())
         };
         run();
     },
-    with_textures: |handle, textures| {
+    with_textures: |handle, textures, _| {
         // return early if we detect this material is not Maya's PBR material
         let mat_maya_type = handle.get_i32("Maya|TypeId");
         if mat_maya_type != Some(maya_consts::PBR_TYPE_ID) {
@@ -0,0 +1,221 @@
+//! Find the image files of FBX textures.
+//!
+//! FBX files only embed the path of texture images on the machine that
+//! exported them, which is often not where the images are when loading the file.
+//! The [`TextureResolver`] of the [`FbxTextureResolver`] resource is responsible
+//! for finding them.
+//!
+//! [`FbxTextureResolver`]: crate::FbxTextureResolver
+
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
+
+use bevy::asset::AssetIo;
+
+/// Finds the image files of textures that are not embedded in the FBX file.
+///
+/// Implement this to override how texture images are looked up,
+/// and insert it in the [`FbxTextureResolver`] resource.
+///
+/// [`FbxTextureResolver`]: crate::FbxTextureResolver
+pub trait TextureResolver: Send + Sync + 'static {
+    /// The asset paths the image of a texture may be at, in order of preference.
+    ///
+    /// The loader reads the first one that exists, the texture is
+    /// missing if none of them do. `asset_io` can be used to check which
+    /// files exist, though not all [`AssetIo`] support it.
+    fn resolve(&self, names: &TextureFileNames, asset_io: &dyn AssetIo) -> Vec<PathBuf>;
+}
+
+/// The file names of a texture image, as written in the FBX file.
+///
+/// They may use `\` as path separator.
+#[derive(Debug, Clone, Copy)]
+pub struct TextureFileNames<'a> {
+    /// The asset path of the FBX file.
+    pub fbx_path: &'a Path,
+    /// The `RelativeFilename` of the texture's `Video`, relative to the FBX file.
+    pub relative_filename: Option<&'a str>,
+    /// The `Filename` of the texture's `Video`, usually an absolute path.
+    pub filename: Option<&'a str>,
+}
+
+/// The default [`TextureResolver`].
+///
+/// It looks for, in order:
+/// 1. The `RelativeFilename` of the texture, relative to the FBX file.
+/// 2. The file name of the texture's `Filename`, in the directory of the FBX file.
+/// 3. The file name of the texture in each of the [`Self::search_dirs`].
+///
+/// If the [`AssetIo`] can't tell which files exist, as on the web and Android,
+/// it returns all of those paths and lets the loader try them in order.
+/// [`Self::case_insensitive`] has no effect then.
+#[derive(Debug, Clone, Default)]
+pub struct DefaultTextureResolver {
+    /// Directories to look for texture images in, relative to the directory
+    /// of the FBX file, for example `"textures"` or `"../textures"`.
+    pub search_dirs: Vec<PathBuf>,
+    /// Whether to ignore the case of file and directory names,
+    /// for files authored on case-insensitive file systems.
+    ///
+    /// Note that this lists the content of directories when an exact match
+    /// can't be found, which can be slow.
+    pub case_insensitive: bool,
+}
+impl TextureResolver for DefaultTextureResolver {
+    fn resolve(&self, names: &TextureFileNames, asset_io: &dyn AssetIo) -> Vec<PathBuf> {
+        let parent = names.fbx_path.parent().unwrap_or(Path::new(""));
+        let relative_filename = names.relative_filename.map(|name| name.replace('\\', "/"));
+        let file_name = names
+            .filename
+            .or(names.relative_filename)
+            .and_then(|name| name.rsplit(['/', '\\']).next())
+            .filter(|name| !name.is_empty());
+
+        let relative = relative_filename.map(|name| parent.join(name));
+        let in_fbx_dir = file_name.map(|name| parent.join(name));
+        let in_search_dirs = self
+            .search_dirs
+            .iter()
+            .filter_map(|dir| Some(parent.join(dir).join(file_name?)));
+
+        let mut candidates = relative.into_iter().chain(in_fbx_dir).chain(in_search_dirs);
+        // The FBX file being loaded exists, so if we can't get its metadata,
+        // `asset_io` doesn't support checking for files.
+        if asset_io.get_metadata(names.fbx_path).is_err() {
+            let mut paths: Vec<PathBuf> = Vec::new();
+            for path in candidates {
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+            return paths;
+        }
+        candidates
+            .find_map(|path| self.find(&path, asset_io))
+            .into_iter()
+            .collect()
+    }
+}
+impl DefaultTextureResolver {
+    fn find(&self, path: &Path, asset_io: &dyn AssetIo) -> Option<PathBuf> {
+        if asset_io.is_file(path) {
+            return Some(path.to_owned());
+        }
+        if !self.case_insensitive {
+            return None;
+        }
+        let mut found = PathBuf::new();
+        for component in path.components() {
+            let exact = found.join(component);
+            if asset_io.get_metadata(&exact).is_ok() {
+                found = exact;
+                continue;
+            }
+            let name = component.as_os_str().to_str()?;
+            let same_name = |entry: &PathBuf| {
+                let entry_name = entry.file_name().and_then(OsStr::to_str);
+                entry_name.is_some_and(|entry_name| entry_name.eq_ignore_ascii_case(name))
+            };
+            found = asset_io.read_directory(&found).ok()?.find(same_name)?;
+        }
+        asset_io.is_file(&found).then_some(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        asset::{AssetIoError, FileType, Metadata},
+        utils::BoxedFuture,
+    };
+
+    use super::*;
+
+    /// An [`AssetIo`] with the given files, or one that can't check files
+    /// if `files` is `None`, like the web and Android ones.
+    struct StubAssetIo {
+        files: Option<&'static [&'static str]>,
+    }
+    impl AssetIo for StubAssetIo {
+        fn load_path<'a>(
+            &'a self,
+            path: &'a Path,
+        ) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+            Box::pin(async move { Err(AssetIoError::NotFound(path.to_owned())) })
+        }
+        fn read_directory(
+            &self,
+            path: &Path,
+        ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+            Err(AssetIoError::NotFound(path.to_owned()))
+        }
+        fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+            let files = self.files.ok_or_else(|| {
+                let unsupported = std::io::ErrorKind::Unsupported.into();
+                AssetIoError::Io(unsupported)
+            })?;
+            if files.iter().any(|file| Path::new(file) == path) {
+                Ok(Metadata::new(FileType::File))
+            } else {
+                Err(AssetIoError::NotFound(path.to_owned()))
+            }
+        }
+        fn watch_path_for_changes(&self, _: &Path, _: Option<PathBuf>) -> Result<(), AssetIoError> {
+            Ok(())
+        }
+        fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+            Ok(())
+        }
+    }
+
+    fn file_names<'a>(relative_filename: &'a str, filename: &'a str) -> TextureFileNames<'a> {
+        TextureFileNames {
+            fbx_path: Path::new("models/chair.fbx"),
+            relative_filename: Some(relative_filename),
+            filename: Some(filename),
+        }
+    }
+
+    #[test]
+    fn resolve_existing_file() {
+        let asset_io = StubAssetIo {
+            files: Some(&["models/chair.fbx", "models/Wood.png"]),
+        };
+        let resolver = DefaultTextureResolver::default();
+        let names = file_names("textures\\Wood.png", "C:\\art\\Wood.png");
+        let paths = resolver.resolve(&names, &asset_io);
+        assert_eq!(paths, [PathBuf::from("models/Wood.png")]);
+
+        let names = file_names("Missing.png", "C:\\art\\Missing.png");
+        assert!(resolver.resolve(&names, &asset_io).is_empty());
+    }
+
+    #[test]
+    fn resolve_without_metadata() {
+        let asset_io = StubAssetIo { files: None };
+        let resolver = DefaultTextureResolver {
+            search_dirs: vec!["../textures".into()],
+            case_insensitive: true,
+        };
+        let names = file_names("textures\\Wood.png", "C:\\art\\Wood.png");
+        let paths = resolver.resolve(&names, &asset_io);
+        let expected = [
+            "models/textures/Wood.png",
+            "models/Wood.png",
+            "models/../textures/Wood.png",
+        ];
+        assert_eq!(paths, expected.map(PathBuf::from));
+
+        // The same path is only tried once.
+        let names = TextureFileNames {
+            relative_filename: Some("Wood.png"),
+            ..names
+        };
+        let paths = resolver.resolve(&names, &asset_io);
+        let expected = ["models/Wood.png", "models/../textures/Wood.png"];
+        assert_eq!(paths, expected.map(PathBuf::from));
+    }
+}
@@ -1,15 +1,18 @@
 //! Collection of temporary extensions to the fbxcell_dom types
 //! until they are merged upstream.
 
-use bevy::math::{DVec2, DVec3, DVec4, EulerRot, Vec2, Vec3, Vec4};
+use bevy::math::{DMat4, DVec2, DVec3, DVec4, EulerRot, Mat3, Mat4, Vec2, Vec3, Vec4};
 use mint::{Vector2, Vector3, Vector4};
 
 use fbxcel_dom::{
     fbxcel::low::v7400::AttributeValue,
     v7400::{
         object::{
+            deformer::{BlendShapeChannelHandle, ClusterHandle},
+            geometry::ShapeHandle,
             material::MaterialHandle,
             model::ModelHandle,
+            nodeattribute::TypedNodeAttributeHandle,
             property::{
                 loaders::{MintLoader, PrimitiveLoader, RgbLoader},
                 LoadProperty, ObjectProperties, PropertyHandle,
@@ -23,10 +26,15 @@ use fbxcel_dom::{
 use rgb::{RGB, RGBA};
 
 pub trait MaterialHandleExt<'a> {
-    fn load_texture(&self, name: &'static str) -> Option<TextureHandle>;
+    fn load_texture(&self, name: &'static str) -> Option<TextureHandle<'a>>;
+    /// The name of the UV set the textures of this material are mapped to.
+    ///
+    /// This is the `UVSet` of the diffuse texture, or of any other texture if
+    /// there is no diffuse texture. `None` if the textures use the default UV set.
+    fn texture_uv_set(&self) -> Option<&'a str>;
 }
 impl<'a> MaterialHandleExt<'a> for MaterialHandle<'a> {
-    fn load_texture(&self, name: &'static str) -> Option<TextureHandle> {
+    fn load_texture(&self, name: &'static str) -> Option<TextureHandle<'a>> {
         self.source_objects()
             .filter(|obj| obj.label() == Some(name))
             .filter_map(|obj| obj.object_handle())
@@ -35,8 +43,21 @@ impl<'a> MaterialHandleExt<'a> for MaterialHandle<'a> {
                 _ => None,
             })
     }
+    fn texture_uv_set(&self) -> Option<&'a str> {
+        let texture = self.load_texture("DiffuseColor").or_else(|| {
+            self.source_objects()
+                .filter_map(|obj| obj.object_handle())
+                .find_map(|obj| match obj.get_typed() {
+                    TypedObjectHandle::Texture(o) => Some(o),
+                    _ => None,
+                })
+        })?;
+        let uv_set = texture.properties().uv_set().ok()??;
+        (!uv_set.is_empty() && uv_set != "default").then_some(uv_set)
+    }
 }
 
+#[allow(dead_code)]
 pub trait MaterialHandleQuickPropsExt<'a> {
     fn get_f32(&self, field: &str) -> Option<f32>;
     fn get_u32(&self, field: &str) -> Option<u32>;
@@ -67,16 +88,196 @@ impl<'a> MaterialHandleQuickPropsExt<'a> for MaterialHandle<'a> {
 }
 
 pub trait GlobalSettingsExt<'a> {
+    /// The unit of the file, in centimeters.
+    ///
+    /// This is `UnitScaleFactor`, the unit the coordinates of the file are in.
+    /// `OriginalUnitScaleFactor` is only a fallback for files missing it:
+    /// when the unit was changed on export, the coordinates were converted
+    /// to `UnitScaleFactor`, the original unit is only informative.
     fn fbx_scale(&self) -> Option<f64>;
+    /// The change of basis from the file axis system to bevy's Y-up right-handed
+    /// axis system, read from `UpAxis`, `FrontAxis`, `CoordAxis` and their signs.
+    ///
+    /// `None` if the properties are missing or don't form a valid basis.
+    fn axis_system(&self) -> Option<Mat3>;
+    /// The frame rate of the file's animations, in frames per second,
+    /// read from `TimeMode` and `CustomFrameRate`.
+    ///
+    /// `None` for the default time mode.
+    #[cfg(feature = "animation")]
+    fn frame_rate(&self) -> Option<f64>;
 }
 impl<'a> GlobalSettingsExt<'a> for GlobalSettings<'a> {
     fn fbx_scale(&self) -> Option<f64> {
-        let prop = self.raw_properties().get_property("UnitScaleFactor")?;
-        let attribute = prop.value_part().get(0)?;
-        match attribute {
-            AttributeValue::F64(scale) => Some(*scale),
-            _ => None,
-        }
+        let get_f64 = |name: &str| {
+            let prop = self.raw_properties().get_property(name)?;
+            match prop.value_part().first()? {
+                AttributeValue::F64(scale) if *scale > 0.0 => Some(*scale),
+                _ => None,
+            }
+        };
+        get_f64("UnitScaleFactor").or_else(|| get_f64("OriginalUnitScaleFactor"))
+    }
+    fn axis_system(&self) -> Option<Mat3> {
+        let get_i32 = |name: &str| {
+            let prop = self.raw_properties().get_property(name)?;
+            match prop.value_part().first()? {
+                AttributeValue::I32(value) => Some(*value),
+                _ => None,
+            }
+        };
+        let axis = |name: &str| {
+            let index = get_i32(name)?;
+            let sign = get_i32(&format!("{name}Sign"))?;
+            let mut axis = Vec3::ZERO;
+            *axis.as_mut().get_mut(usize::try_from(index).ok()?)? = sign.signum() as f32;
+            Some(axis)
+        };
+        // The file's right, up and front (toward the viewer) axes
+        // become bevy's +X, +Y and +Z.
+        let right = axis("CoordAxis")?;
+        let up = axis("UpAxis")?;
+        let front = axis("FrontAxis")?;
+        let basis = Mat3::from_cols(right, up, front).transpose();
+        (basis.determinant() != 0.0).then_some(basis)
+    }
+    #[cfg(feature = "animation")]
+    fn frame_rate(&self) -> Option<f64> {
+        let get = |name: &str| {
+            let prop = self.raw_properties().get_property(name)?;
+            prop.value_part().first()
+        };
+        // The values of the FBX SDK `FbxTime::EMode` enum.
+        let rate = match get("TimeMode")?.get_i32()? {
+            1 => 120.0,
+            2 => 100.0,
+            3 => 60.0,
+            4 => 50.0,
+            5 => 48.0,
+            6 | 7 => 30.0,
+            8 | 9 => 29.97,
+            10 => 25.0,
+            11 => 24.0,
+            12 => 1000.0,
+            13 => 23.976,
+            14 => get("CustomFrameRate")?.get_f64()?,
+            15 => 96.0,
+            16 => 72.0,
+            17 => 59.94,
+            18 => 119.88,
+            _ => return None,
+        };
+        (rate > 0.0).then_some(rate)
+    }
+}
+
+pub trait ClusterHandleExt<'a> {
+    /// The control points affected by this cluster.
+    fn indexes(&self) -> &'a [i32];
+    /// How much each control point in `indexes` is affected by this cluster.
+    fn weights(&self) -> &'a [f64];
+    /// The global transform of the deformed mesh at bind time.
+    fn transform(&self) -> Option<Mat4>;
+    /// The global transform of the bone at bind time.
+    fn transform_link(&self) -> Option<Mat4>;
+    /// The model node (usually a `LimbNode`) driving this cluster.
+    fn bone(&self) -> Option<ModelHandle<'a>>;
+}
+impl<'a> ClusterHandleExt<'a> for ClusterHandle<'a> {
+    fn indexes(&self) -> &'a [i32] {
+        self.node()
+            .first_child_by_name("Indexes")
+            .and_then(|n| n.attributes().first()?.get_arr_i32())
+            .unwrap_or_default()
+    }
+    fn weights(&self) -> &'a [f64] {
+        self.node()
+            .first_child_by_name("Weights")
+            .and_then(|n| n.attributes().first()?.get_arr_f64())
+            .unwrap_or_default()
+    }
+    fn transform(&self) -> Option<Mat4> {
+        let node = self.node().first_child_by_name("Transform")?;
+        let matrix = node.attributes().first()?.get_arr_f64()?;
+        (matrix.len() == 16).then(|| DMat4::from_cols_slice(matrix).as_mat4())
+    }
+    fn transform_link(&self) -> Option<Mat4> {
+        let node = self.node().first_child_by_name("TransformLink")?;
+        let matrix = node.attributes().first()?.get_arr_f64()?;
+        (matrix.len() == 16).then(|| DMat4::from_cols_slice(matrix).as_mat4())
+    }
+    fn bone(&self) -> Option<ModelHandle<'a>> {
+        self.source_objects()
+            .filter(|obj| obj.label().is_none())
+            .filter_map(|obj| obj.object_handle())
+            .find_map(|obj| match obj.get_typed() {
+                TypedObjectHandle::Model(o) => Some(*o),
+                _ => None,
+            })
+    }
+}
+
+pub trait BlendShapeChannelHandleExt<'a> {
+    /// The default influence of this channel, in percent (from 0 to 100).
+    fn deform_percent(&self) -> f64;
+}
+impl<'a> BlendShapeChannelHandleExt<'a> for BlendShapeChannelHandle<'a> {
+    fn deform_percent(&self) -> f64 {
+        let properties = self.properties_by_native_typename("FbxBlendShapeChannel");
+        let from_properties = f64::get_property(properties, "DeformPercent").ok();
+        // Older exporters only write the value as a direct child node.
+        let from_node = || {
+            let node = self.node().first_child_by_name("DeformPercent")?;
+            node.attributes().first()?.get_f64()
+        };
+        from_properties.or_else(from_node).unwrap_or(0.0)
+    }
+}
+
+pub trait ShapeHandleExt<'a> {
+    /// The control points of the base mesh moved by this shape.
+    fn indexes(&self) -> &'a [i32];
+    /// The position offset of each control point in `indexes`, as flat `xyz` triplets.
+    fn vertices(&self) -> &'a [f64];
+    /// The normal offset of each control point in `indexes`, as flat `xyz` triplets.
+    ///
+    /// This is empty when the exporter didn't write normals for the shape.
+    fn normals(&self) -> &'a [f64];
+}
+impl<'a> ShapeHandleExt<'a> for ShapeHandle<'a> {
+    fn indexes(&self) -> &'a [i32] {
+        self.node()
+            .first_child_by_name("Indexes")
+            .and_then(|n| n.attributes().first()?.get_arr_i32())
+            .unwrap_or_default()
+    }
+    fn vertices(&self) -> &'a [f64] {
+        self.node()
+            .first_child_by_name("Vertices")
+            .and_then(|n| n.attributes().first()?.get_arr_f64())
+            .unwrap_or_default()
+    }
+    fn normals(&self) -> &'a [f64] {
+        self.node()
+            .first_child_by_name("Normals")
+            .and_then(|n| n.attributes().first()?.get_arr_f64())
+            .unwrap_or_default()
+    }
+}
+
+pub trait ModelHandleExt<'a> {
+    /// The `NodeAttribute` defining what this node is (camera, light etc.)
+    fn node_attribute(&self) -> Option<TypedNodeAttributeHandle<'a>>;
+}
+impl<'a> ModelHandleExt<'a> for ModelHandle<'a> {
+    fn node_attribute(&self) -> Option<TypedNodeAttributeHandle<'a>> {
+        self.source_objects()
+            .filter(|obj| obj.label().is_none())
+            .filter_map(|obj| obj.object_handle())
+            .find_map(|obj| match obj.get_typed() {
+                TypedObjectHandle::NodeAttribute(o) => Some(o),
+                _ => None,
+            })
     }
 }
 
@@ -0,0 +1,295 @@
+//! Read the layer elements fbxcel-dom doesn't expose, such as
+//! `LayerElementTangent`, `LayerElementBinormal` and `LayerElementSmoothing`,
+//! or doesn't read correctly, such as `LayerElementColor`.
+
+use std::ops::Range;
+
+use anyhow::{bail, Context};
+use fbxcel_dom::{
+    fbxcel::low::v7400::AttributeValue,
+    v7400::{
+        data::mesh::{
+            layer::{MappingMode, ReferenceMode},
+            TriangleVertices,
+        },
+        object::geometry::MeshHandle,
+    },
+};
+
+/// The range of each polygon in the raw polygon vertices of a mesh.
+///
+/// The last vertex of each polygon is stored as `!control_point_index`.
+pub(crate) fn polygon_ranges(raw_polygon_vertices: &[i32]) -> Vec<Range<usize>> {
+    let mut start = 0;
+    let mut ranges = Vec::new();
+    for (i, vertex) in raw_polygon_vertices.iter().enumerate() {
+        if *vertex < 0 {
+            ranges.push(start..i + 1);
+            start = i + 1;
+        }
+    }
+    ranges
+}
+
+/// The control point index of a raw polygon vertex.
+pub(crate) fn control_point(raw_polygon_vertex: i32) -> usize {
+    raw_polygon_vertex.max(!raw_polygon_vertex) as usize
+}
+
+/// Indices of the triangle corners of a mesh in the layer element
+/// arrays, for each mapping mode.
+pub(crate) struct TriangleCorners {
+    pub(crate) control_points: Vec<usize>,
+    pub(crate) polygon_vertices: Vec<usize>,
+    pub(crate) polygons: Vec<usize>,
+}
+impl TriangleCorners {
+    pub(crate) fn new(triangles: &TriangleVertices) -> anyhow::Result<Self> {
+        // fbxcel-dom doesn't expose the polygon vertex index of triangle corners,
+        // so we find them back from the polygon index and control point index.
+        let polygon_vertices = triangles.polygon_vertices();
+        let raw_polygon_vertices = polygon_vertices.raw_polygon_vertices();
+        let polygon_ranges = polygon_ranges(raw_polygon_vertices);
+        let mut corners = TriangleCorners {
+            control_points: Vec::with_capacity(triangles.len()),
+            polygon_vertices: Vec::with_capacity(triangles.len()),
+            polygons: Vec::with_capacity(triangles.len()),
+        };
+        for tri_vi in triangles.triangle_vertex_indices() {
+            let cpi = triangles
+                .control_point_index(tri_vi)
+                .context("Failed to get control point index")?
+                .to_u32() as usize;
+            let polygon = triangles
+                .polygon_index(tri_vi.triangle_index())
+                .context("Failed to get polygon index")?
+                .to_usize();
+            let range = polygon_ranges
+                .get(polygon)
+                .context("Polygon index out of range")?;
+            let pvi = raw_polygon_vertices[range.clone()]
+                .iter()
+                .position(|&vertex| control_point(vertex) == cpi)
+                .context("Triangle corner not in its polygon")?;
+            corners.control_points.push(cpi);
+            corners.polygon_vertices.push(range.start + pvi);
+            corners.polygons.push(polygon);
+        }
+        Ok(corners)
+    }
+}
+
+/// A layer element of a mesh, read directly from the FBX nodes.
+pub(crate) struct RawLayerElement<'a> {
+    mapping: MappingMode,
+    reference: ReferenceMode,
+    name: String,
+    data: &'a AttributeValue,
+    index: &'a [i32],
+}
+impl<'a> RawLayerElement<'a> {
+    /// The `element` layer element of `mesh` with the lowest `TypedIndex`,
+    /// its values are stored in the `data` child node.
+    ///
+    /// `None` if `mesh` has no such layer element.
+    pub(crate) fn find(
+        mesh: &MeshHandle<'a>,
+        element: &str,
+        data: &str,
+    ) -> anyhow::Result<Option<Self>> {
+        Self::find_indexed(mesh, element, data, &format!("{data}Index"))
+    }
+
+    /// Like [`Self::find`], for layer elements with an index node not
+    /// named after the data node, such as the `ColorIndex` of `Colors`.
+    pub(crate) fn find_indexed(
+        mesh: &MeshHandle<'a>,
+        element: &str,
+        data: &str,
+        index: &str,
+    ) -> anyhow::Result<Option<Self>> {
+        let node = mesh
+            .node()
+            .children_by_name(element)
+            .min_by_key(|node| node.attributes().first().and_then(|a| a.get_i32()));
+        let Some(node) = node else {
+            return Ok(None);
+        };
+        let get_str = |name: &str| {
+            node.first_child_by_name(name)
+                .and_then(|n| n.attributes().first()?.get_string())
+                .with_context(|| format!("{element} has no {name}"))
+        };
+        let get_arr = |name: &str| {
+            node.first_child_by_name(name)
+                .and_then(|n| n.attributes().first())
+        };
+        let element = RawLayerElement {
+            mapping: get_str("MappingInformationType")?.parse()?,
+            reference: get_str("ReferenceInformationType")?.parse()?,
+            name: element.to_owned(),
+            data: get_arr(data).with_context(|| format!("{element} has no {data}"))?,
+            index: get_arr(index)
+                .and_then(|a| a.get_arr_i32())
+                .unwrap_or_default(),
+        };
+        Ok(Some(element))
+    }
+
+    pub(crate) fn mapping(&self) -> MappingMode {
+        self.mapping
+    }
+
+    /// The index in the data array of the `index`th mapped item.
+    fn data_index(&self, index: usize) -> anyhow::Result<usize> {
+        match self.reference {
+            ReferenceMode::Direct => Ok(index),
+            ReferenceMode::IndexToDirect => {
+                let index = self.index.get(index).context("Index out of range")?;
+                usize::try_from(*index).context("Negative index")
+            }
+        }
+    }
+
+    /// The values of this layer element for each triangle corner, as `xyz` triplets.
+    pub(crate) fn vec3s(&self, corners: &TriangleCorners) -> anyhow::Result<Vec<[f32; 3]>> {
+        self.vectors(corners)
+    }
+
+    /// The values of this layer element for each triangle corner, as `rgba` colors.
+    pub(crate) fn vec4s(&self, corners: &TriangleCorners) -> anyhow::Result<Vec<[f32; 4]>> {
+        self.vectors(corners)
+    }
+
+    /// The values of this layer element for each triangle corner, by groups of `N`.
+    fn vectors<const N: usize>(&self, corners: &TriangleCorners) -> anyhow::Result<Vec<[f32; N]>> {
+        let data = self
+            .data
+            .get_arr_f64()
+            .with_context(|| format!("{} data is not a f64 array", self.name))?;
+        let indices = match self.mapping {
+            MappingMode::ByControlPoint => &corners.control_points,
+            MappingMode::ByPolygonVertex => &corners.polygon_vertices,
+            MappingMode::ByPolygon => &corners.polygons,
+            MappingMode::AllSame => &vec![0; corners.polygons.len()],
+            mode => bail!("Unsupported mapping mode: {mode:?}"),
+        };
+        indices
+            .iter()
+            .map(|&index| {
+                let index = self.data_index(index)?;
+                match data.get(index * N..index * N + N) {
+                    Some(values) => Ok(std::array::from_fn(|i| values[i] as f32)),
+                    None => bail!("Layer element data out of range: index={index}"),
+                }
+            })
+            .collect()
+    }
+
+    /// The value of this layer element for each of the `count` mapped items
+    /// (polygons, edges etc. depending on [`Self::mapping`]).
+    pub(crate) fn i32s(&self, count: usize) -> anyhow::Result<Vec<i32>> {
+        let data = self
+            .data
+            .get_arr_i32()
+            .with_context(|| format!("{} data is not a i32 array", self.name))?;
+        (0..count)
+            .map(|index| {
+                let index = match self.mapping {
+                    MappingMode::AllSame => 0,
+                    _ => index,
+                };
+                let index = self.data_index(index)?;
+                data.get(index)
+                    .copied()
+                    .with_context(|| format!("Layer element data out of range: index={index}"))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fbxcel_dom::v7400::{
+        object::{geometry::TypedGeometryHandle, TypedObjectHandle},
+        Document, Loader,
+    };
+
+    use super::*;
+    use crate::{ascii, utils::triangulate::triangulate};
+
+    /// A quad with an `IndexToDirect` color layer, as written by Blender and Maya.
+    const QUAD: &[u8] = br#"
+FBXHeaderExtension:  {
+    FBXVersion: 7400
+}
+Documents:  {
+    Count: 1
+    Document: 1, "", "Scene" {
+        RootNode: 0
+    }
+}
+Objects:  {
+    Geometry: 2, "Geometry::Quad", "Mesh" {
+        Vertices: *12 {
+            a: 0,0,0,1,0,0,1,1,0,0,1,0
+        }
+        PolygonVertexIndex: *4 {
+            a: 0,1,2,-4
+        }
+        LayerElementColor: 0 {
+            Version: 101
+            Name: "Col"
+            MappingInformationType: "ByPolygonVertex"
+            ReferenceInformationType: "IndexToDirect"
+            Colors: *8 {
+                a: 1,0,0,1,0,0,1,0.5
+            }
+            ColorIndex: *4 {
+                a: 0,1,1,0
+            }
+        }
+    }
+}
+Connections:  {
+}
+"#;
+
+    fn quad(doc: &Document) -> MeshHandle<'_> {
+        doc.objects()
+            .find_map(|obj| match obj.get_typed() {
+                TypedObjectHandle::Geometry(TypedGeometryHandle::Mesh(mesh)) => Some(mesh),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn index_to_direct_colors() {
+        let tree = ascii::parse(QUAD).unwrap();
+        let doc = Loader::new().load_from_tree(tree).unwrap();
+        let mesh = quad(&doc);
+        let triangles = mesh
+            .polygon_vertices()
+            .unwrap()
+            .triangulate_each(triangulate)
+            .unwrap();
+        let corners = TriangleCorners::new(&triangles).unwrap();
+
+        let colors =
+            RawLayerElement::find_indexed(&mesh, "LayerElementColor", "Colors", "ColorIndex");
+        let colors = colors.unwrap().unwrap().vec4s(&corners).unwrap();
+        let (red, blue) = ([1.0, 0.0, 0.0, 1.0], [0.0, 0.0, 1.0, 0.5]);
+        let expected: Vec<_> = corners
+            .control_points
+            .iter()
+            .map(|&cpi| if cpi == 0 || cpi == 3 { red } else { blue })
+            .collect();
+        assert_eq!(colors.len(), 6);
+        assert_eq!(colors, expected);
+
+        // fbxcel-dom looks for a `ColorsIndex` node instead.
+        let colors = RawLayerElement::find(&mesh, "LayerElementColor", "Colors");
+        assert!(colors.unwrap().unwrap().vec4s(&corners).is_err());
+    }
+}
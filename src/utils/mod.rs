@@ -1,2 +1,5 @@
 pub(crate) mod fbx_extend;
+pub(crate) mod layer;
+pub(crate) mod normals;
 pub(crate) mod triangulate;
+pub(crate) mod weld;
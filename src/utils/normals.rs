@@ -0,0 +1,474 @@
+//! Compute normals of meshes without a `LayerElementNormal`.
+
+// a bit of trivia on how smoothing is encoded in FBX:
+// - `LayerElementSmoothing` is either mapped `ByPolygon` or `ByEdge`.
+// - `ByPolygon` smoothing holds 3ds Max style smoothing groups: a bit field per
+//   polygon, two polygons are smoothed together if they share a smoothing group.
+//   Exporters with a single "smooth" flag per polygon write `0` or `1`.
+// - `ByEdge` smoothing (written by Maya and Blender) tells for each edge whether
+//   it is smooth (`1`) or hard (`0`). Edges are listed in the `Edges` node of the
+//   geometry, as the polygon vertex index of their first vertex.
+use anyhow::Context;
+use bevy::{
+    math::Vec3,
+    prelude::debug,
+    utils::{HashMap, HashSet},
+};
+use fbxcel_dom::v7400::{
+    data::mesh::{layer::MappingMode, TriangleVertices},
+    object::geometry::MeshHandle,
+};
+
+use crate::{
+    utils::layer::{control_point, polygon_ranges, RawLayerElement, TriangleCorners},
+    GeneratedNormals,
+};
+
+/// Which polygons sharing a control point are smoothed together.
+pub(crate) enum Smoothing {
+    /// Smoothing group bit field of each polygon.
+    Groups(Vec<i32>),
+    /// The hard edges, as the sorted pair of their control points.
+    HardEdges(HashSet<(usize, usize)>),
+    /// Maximum angle, in radians, between the normals of smoothed polygons.
+    Angle(f32),
+    /// All polygons are smoothed together.
+    All,
+}
+impl Smoothing {
+    /// Read the smoothing of `mesh`, falling back to `settings` if the
+    /// mesh doesn't have smoothing information.
+    pub(crate) fn load(
+        mesh: &MeshHandle,
+        raw_polygon_vertices: &[i32],
+        settings: GeneratedNormals,
+    ) -> anyhow::Result<Self> {
+        let fallback = match settings {
+            GeneratedNormals::AngleThreshold(angle) => Smoothing::Angle(angle),
+            _ => Smoothing::All,
+        };
+        let Some(element) = RawLayerElement::find(mesh, "LayerElementSmoothing", "Smoothing")?
+        else {
+            return Ok(fallback);
+        };
+        let polygon_ranges = polygon_ranges(raw_polygon_vertices);
+        let smoothing = match element.mapping() {
+            MappingMode::ByPolygon | MappingMode::AllSame => {
+                let groups = element.i32s(polygon_ranges.len())?;
+                Smoothing::Groups(groups)
+            }
+            MappingMode::ByEdge => {
+                let edges = mesh
+                    .node()
+                    .first_child_by_name("Edges")
+                    .and_then(|n| n.attributes().first()?.get_arr_i32())
+                    .context("Smoothing by edge without Edges")?;
+                let smooth = element.i32s(edges.len())?;
+                // For each polygon vertex, the next vertex in its polygon.
+                let mut next = vec![0; raw_polygon_vertices.len()];
+                for range in polygon_ranges {
+                    for pvi in range.clone() {
+                        next[pvi] = if pvi + 1 == range.end {
+                            range.start
+                        } else {
+                            pvi + 1
+                        };
+                    }
+                }
+                let mut hard_edges = HashSet::new();
+                for (&pvi, smooth) in edges.iter().zip(smooth) {
+                    let pvi = pvi as usize;
+                    let (Some(start), Some(next)) = (raw_polygon_vertices.get(pvi), next.get(pvi))
+                    else {
+                        continue;
+                    };
+                    let end = raw_polygon_vertices[*next];
+                    if smooth == 0 {
+                        hard_edges.insert(edge_key(control_point(*start), control_point(end)));
+                    }
+                }
+                Smoothing::HardEdges(hard_edges)
+            }
+            mode => {
+                debug!("Unsupported smoothing mapping mode: {mode:?}");
+                fallback
+            }
+        };
+        Ok(smoothing)
+    }
+}
+
+fn edge_key(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+/// Union-find, see <https://en.wikipedia.org/wiki/Disjoint-set_data_structure>.
+struct DisjointSets(Vec<usize>);
+impl DisjointSets {
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.0[i] != i {
+            self.0[i] = self.0[self.0[i]];
+            i = self.0[i];
+        }
+        i
+    }
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        self.0[a] = b;
+    }
+}
+
+/// The normal of each triangle of `positions`, weighted by the triangle area.
+fn face_normals(positions: &[[f32; 3]]) -> impl Iterator<Item = Vec3> + '_ {
+    positions.chunks_exact(3).map(|triangle| {
+        let [a, b, c] = [0, 1, 2].map(|i| Vec3::from(triangle[i]));
+        (b - a).cross(c - a)
+    })
+}
+
+/// Normals of the triangle corners of `mesh`, generated according to `settings`.
+///
+/// `positions` are the positions of the triangle corners, three per triangle.
+pub(crate) fn generate_normals(
+    mesh: &MeshHandle,
+    triangles: &TriangleVertices,
+    positions: &[[f32; 3]],
+    settings: GeneratedNormals,
+) -> anyhow::Result<Vec<[f32; 3]>> {
+    if let GeneratedNormals::Flat = settings {
+        return Ok(flat_normals(positions));
+    }
+    let polygon_vertices = triangles.polygon_vertices();
+    let raw_polygon_vertices = polygon_vertices.raw_polygon_vertices();
+    let smoothing = Smoothing::load(mesh, raw_polygon_vertices, settings)
+        .context("Failed to read smoothing")?;
+    let corners = TriangleCorners::new(triangles)?;
+    Ok(smooth_normals(
+        positions,
+        &corners,
+        raw_polygon_vertices,
+        &smoothing,
+    ))
+}
+
+/// Normals of triangle corners, using the normal of the triangle they are part of.
+///
+/// `positions` are the positions of the triangle corners, three per triangle.
+pub(crate) fn flat_normals(positions: &[[f32; 3]]) -> Vec<[f32; 3]> {
+    face_normals(positions)
+        .flat_map(|normal| [normal.normalize_or_zero().into(); 3])
+        .collect()
+}
+
+/// Normals of triangle corners, averaging the normals of the polygons
+/// sharing the same control point, as long as `smoothing` allows it.
+///
+/// `positions` are the positions of the triangle corners, three per triangle.
+pub(crate) fn smooth_normals(
+    positions: &[[f32; 3]],
+    corners: &TriangleCorners,
+    raw_polygon_vertices: &[i32],
+    smoothing: &Smoothing,
+) -> Vec<[f32; 3]> {
+    let triangle_normals: Vec<_> = face_normals(positions).collect();
+
+    // Each polygon corner is a node, nodes with the same normal end up in the same set.
+    let mut node_indices = HashMap::new();
+    let mut nodes = Vec::new();
+    let corner_nodes: Vec<usize> = corners
+        .polygons
+        .iter()
+        .zip(&corners.control_points)
+        .map(|(&polygon, &point)| {
+            *node_indices.entry((polygon, point)).or_insert_with(|| {
+                nodes.push((polygon, point));
+                nodes.len() - 1
+            })
+        })
+        .collect();
+    let mut sets = DisjointSets((0..nodes.len()).collect());
+
+    let mut point_nodes: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (node, &(_, point)) in nodes.iter().enumerate() {
+        point_nodes.entry(point).or_default().push(node);
+    }
+    match smoothing {
+        Smoothing::Groups(groups) => {
+            let group = |polygon: usize| groups.get(polygon).copied().unwrap_or(0);
+            for point_nodes in point_nodes.values() {
+                for (i, &a) in point_nodes.iter().enumerate() {
+                    for &b in &point_nodes[i + 1..] {
+                        if group(nodes[a].0) & group(nodes[b].0) != 0 {
+                            sets.union(a, b);
+                        }
+                    }
+                }
+            }
+        }
+        Smoothing::Angle(max_angle) => {
+            let mut polygon_normals = HashMap::<usize, Vec3>::new();
+            for (triangle, normal) in triangle_normals.iter().enumerate() {
+                let polygon = corners.polygons[triangle * 3];
+                *polygon_normals.entry(polygon).or_default() += *normal;
+            }
+            for point_nodes in point_nodes.values() {
+                for (i, &a) in point_nodes.iter().enumerate() {
+                    for &b in &point_nodes[i + 1..] {
+                        let normal_a = polygon_normals[&nodes[a].0];
+                        let normal_b = polygon_normals[&nodes[b].0];
+                        let angle = normal_a.angle_between(normal_b);
+                        // `angle_between` is NaN for degenerate polygons.
+                        if angle <= *max_angle || angle.is_nan() {
+                            sets.union(a, b);
+                        }
+                    }
+                }
+            }
+        }
+        Smoothing::All => {
+            for point_nodes in point_nodes.values() {
+                for &node in &point_nodes[1..] {
+                    sets.union(point_nodes[0], node);
+                }
+            }
+        }
+        Smoothing::HardEdges(hard_edges) => {
+            // Polygons are smoothed together on both ends of their shared smooth edges.
+            let mut edge_polygons: HashMap<_, Vec<usize>> = HashMap::new();
+            for (polygon, range) in polygon_ranges(raw_polygon_vertices).into_iter().enumerate() {
+                let points = raw_polygon_vertices[range]
+                    .iter()
+                    .map(|&v| control_point(v));
+                let next_points = points.clone().cycle().skip(1);
+                for (a, b) in points.zip(next_points) {
+                    let edge = edge_key(a, b);
+                    if !hard_edges.contains(&edge) {
+                        edge_polygons.entry(edge).or_default().push(polygon);
+                    }
+                }
+            }
+            for ((a, b), polygons) in edge_polygons {
+                for point in [a, b] {
+                    let mut polygon_nodes = polygons
+                        .iter()
+                        .filter_map(|&polygon| node_indices.get(&(polygon, point)));
+                    let Some(&first) = polygon_nodes.next() else {
+                        continue;
+                    };
+                    for &node in polygon_nodes {
+                        sets.union(first, node);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut set_normals = vec![Vec3::ZERO; nodes.len()];
+    for (corner, &node) in corner_nodes.iter().enumerate() {
+        let set = sets.find(node);
+        set_normals[set] += triangle_normals[corner / 3];
+    }
+    corner_nodes
+        .iter()
+        .map(|&node| set_normals[sets.find(node)].normalize_or_zero().into())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use fbxcel_dom::v7400::{
+        object::{geometry::TypedGeometryHandle, TypedObjectHandle},
+        Document, Loader,
+    };
+
+    use super::*;
+    use crate::{ascii, utils::triangulate::triangulate};
+
+    /// A unit cube, with its faces in the -Z, +Z, -Y, +Y, -X, +X order.
+    const CUBE: &str = r#"
+        Vertices: *24 {
+            a: 0,0,0,1,0,0,0,1,0,1,1,0,0,0,1,1,0,1,0,1,1,1,1,1
+        }
+        PolygonVertexIndex: *24 {
+            a: 0,2,3,-2,4,5,7,-7,0,1,5,-5,2,6,7,-4,0,4,6,-3,1,3,7,-6
+        }"#;
+
+    /// A floor quad, with a wall at a 90° angle on its -X edge, and a quad
+    /// bent down by 10° on its +X edge.
+    const FOLDS: &str = r#"
+        Vertices: *24 {
+            a: 0,0,0,1,0,0,1,1,0,0,1,0,0,0,1,0,1,1,2,0,0.17632698,2,1,0.17632698
+        }
+        PolygonVertexIndex: *12 {
+            a: 0,1,2,-4,0,3,5,-5,1,6,7,-3
+        }
+        Edges: *10 {
+            a: 0,1,2,3,5,6,7,8,9,10
+        }"#;
+    const FLOOR: usize = 0;
+    const WALL: usize = 1;
+    const BENT: usize = 2;
+
+    fn smoothing(mapping: &str, values: &str) -> String {
+        let count = values.split(',').count();
+        format!(
+            r#"
+        LayerElementSmoothing: 0 {{
+            Version: 102
+            Name: ""
+            MappingInformationType: "{mapping}"
+            ReferenceInformationType: "Direct"
+            Smoothing: *{count} {{
+                a: {values}
+            }}
+        }}"#
+        )
+    }
+
+    fn document(geometry: &str) -> Document {
+        let fbx = format!(
+            r#"
+FBXHeaderExtension:  {{
+    FBXVersion: 7400
+}}
+Documents:  {{
+    Count: 1
+    Document: 1, "", "Scene" {{
+        RootNode: 0
+    }}
+}}
+Objects:  {{
+    Geometry: 2, "Geometry::Mesh", "Mesh" {{{geometry}
+    }}
+}}
+Connections:  {{
+}}
+"#
+        );
+        let tree = ascii::parse(fbx.as_bytes()).unwrap();
+        Loader::new().load_from_tree(tree).unwrap()
+    }
+
+    /// The generated normal of each polygon corner, by polygon and control point.
+    fn corner_normals(doc: &Document, settings: GeneratedNormals) -> HashMap<(usize, usize), Vec3> {
+        let mesh = doc
+            .objects()
+            .find_map(|obj| match obj.get_typed() {
+                TypedObjectHandle::Geometry(TypedGeometryHandle::Mesh(mesh)) => Some(mesh),
+                _ => None,
+            })
+            .unwrap();
+        let polygon_vertices = mesh.polygon_vertices().unwrap();
+        let triangles = polygon_vertices.triangulate_each(triangulate).unwrap();
+        let positions: Vec<_> = triangles
+            .iter_control_point_indices()
+            .map(|cpi| {
+                let point = polygon_vertices.control_point(cpi.unwrap()).unwrap();
+                [point.x as f32, point.y as f32, point.z as f32]
+            })
+            .collect();
+        let normals = generate_normals(&mesh, &triangles, &positions, settings).unwrap();
+
+        let corners = TriangleCorners::new(&triangles).unwrap();
+        let mut by_corner = HashMap::new();
+        for (i, normal) in normals.into_iter().enumerate() {
+            let corner = (corners.polygons[i], corners.control_points[i]);
+            let previous = by_corner.insert(corner, Vec3::from(normal));
+            // Corners of a polygon on the same control point share their normal.
+            if let Some(previous) = previous {
+                assert!(previous.abs_diff_eq(normal.into(), 1e-5));
+            }
+        }
+        by_corner
+    }
+
+    fn assert_near(normal: Vec3, expected: impl Into<Vec3>) {
+        let expected = expected.into().normalize();
+        assert!(normal.abs_diff_eq(expected, 1e-5), "{normal} != {expected}");
+    }
+
+    /// The outward normal of each face of [`CUBE`].
+    const CUBE_FACES: [[f32; 3]; 6] = [
+        [0.0, 0.0, -1.0],
+        [0.0, 0.0, 1.0],
+        [0.0, -1.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [-1.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0],
+    ];
+
+    #[test]
+    fn smoothing_groups() {
+        // Each face has its own smoothing group, so edges stay hard.
+        let groups = smoothing("ByPolygon", "1,2,4,8,16,32");
+        let doc = document(&format!("{CUBE}{groups}"));
+        let normals = corner_normals(&doc, GeneratedNormals::Smooth);
+        assert_eq!(normals.len(), 24);
+        for ((polygon, _), normal) in normals {
+            assert_near(normal, CUBE_FACES[polygon]);
+        }
+
+        // Faces sharing a smoothing group are smoothed together.
+        let groups = smoothing("ByPolygon", "1,1,1,1,1,1");
+        let doc = document(&format!("{CUBE}{groups}"));
+        let normals = corner_normals(&doc, GeneratedNormals::Smooth);
+        assert_near(normals[&(0, 0)], [-1.0, -1.0, -1.0]);
+        assert_near(normals[&(5, 7)], [1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn flat_ignores_smoothing() {
+        let groups = smoothing("ByPolygon", "1,1,1,1,1,1");
+        let doc = document(&format!("{CUBE}{groups}"));
+        for ((polygon, _), normal) in corner_normals(&doc, GeneratedNormals::Flat) {
+            assert_near(normal, CUBE_FACES[polygon]);
+        }
+
+        let doc = document(FOLDS);
+        let normals = corner_normals(&doc, GeneratedNormals::Flat);
+        assert_near(normals[&(FLOOR, 1)], [0.0, 0.0, 1.0]);
+        assert_near(normals[&(BENT, 1)], [-0.17632698, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn smoothing_by_edge() {
+        // Only the edge between the floor and the bent quad is hard.
+        let edges = smoothing("ByEdge", "1,0,1,1,1,1,1,1,1,1");
+        let doc = document(&format!("{FOLDS}{edges}"));
+        let normals = corner_normals(&doc, GeneratedNormals::Smooth);
+        for point in [0, 3] {
+            assert_near(normals[&(FLOOR, point)], [1.0, 0.0, 1.0]);
+            assert_near(normals[&(WALL, point)], [1.0, 0.0, 1.0]);
+        }
+        for point in [1, 2] {
+            assert_near(normals[&(FLOOR, point)], [0.0, 0.0, 1.0]);
+            assert_near(normals[&(BENT, point)], [-0.17632698, 0.0, 1.0]);
+        }
+    }
+
+    #[test]
+    fn angle_threshold() {
+        let doc = document(FOLDS);
+        let threshold = GeneratedNormals::AngleThreshold(45_f32.to_radians());
+        let normals = corner_normals(&doc, threshold);
+        // Split at the 90° fold.
+        for point in [0, 3] {
+            assert_near(normals[&(FLOOR, point)], [0.0, 0.0, 1.0]);
+            assert_near(normals[&(WALL, point)], [1.0, 0.0, 0.0]);
+        }
+        // Smoothed at the 10° fold.
+        for point in [1, 2] {
+            let floor = normals[&(FLOOR, point)];
+            assert_near(normals[&(BENT, point)], floor);
+            assert!(floor.x < 0.0 && floor.z > 0.0);
+        }
+
+        // Nothing is smoothed together with a lower threshold.
+        let threshold = GeneratedNormals::AngleThreshold(5_f32.to_radians());
+        let normals = corner_normals(&doc, threshold);
+        for point in [1, 2] {
+            assert_near(normals[&(FLOOR, point)], [0.0, 0.0, 1.0]);
+            assert_near(normals[&(BENT, point)], [-0.17632698, 0.0, 1.0]);
+        }
+    }
+}
@@ -0,0 +1,233 @@
+//! Merge identical vertices of a mesh, and remove unused ones.
+
+use bevy::{
+    render::mesh::{Mesh, VertexAttributeValues},
+    utils::HashMap,
+};
+
+/// Result of [`weld_vertices`].
+pub(crate) struct Welded {
+    /// For each original vertex, its index in the welded vertex buffer.
+    pub(crate) remap: Vec<u32>,
+    /// For each welded vertex, the index of the original vertex it is a copy of.
+    pub(crate) kept: Vec<usize>,
+}
+
+/// The comparison key of a float.
+///
+/// Floats are snapped to a grid of `tolerance` sized cells,
+/// or compared exactly if `tolerance` is zero.
+fn float_key(float: f32, tolerance: f32) -> i64 {
+    match () {
+        () if tolerance > 0.0 => (float / tolerance).round() as i64,
+        // Make sure 0.0 and -0.0 are the same key
+        () if float == 0.0 => 0,
+        () => float.to_bits() as i64,
+    }
+}
+
+/// Push the comparison key of the `index`th value of `values` into `key`.
+fn push_key(values: &VertexAttributeValues, index: usize, tolerance: f32, key: &mut Vec<i64>) {
+    let mut push_floats = |floats: &[f32]| {
+        key.extend(floats.iter().map(|&float| float_key(float, tolerance)));
+    };
+    match values {
+        VertexAttributeValues::Float32(values) => push_floats(&values[index..=index]),
+        VertexAttributeValues::Float32x2(values) => push_floats(&values[index]),
+        VertexAttributeValues::Float32x3(values) => push_floats(&values[index]),
+        VertexAttributeValues::Float32x4(values) => push_floats(&values[index]),
+        values => {
+            let bytes = values.get_bytes();
+            let size = bytes.len() / values.len();
+            let value = &bytes[index * size..(index + 1) * size];
+            key.extend(value.iter().map(|&byte| byte as i64));
+        }
+    }
+}
+
+/// Find the vertices of `mesh` with identical attributes.
+///
+/// `extra` are additional per-vertex values that are not stored
+/// as a vertex attribute, but must be equal for vertices to be merged.
+///
+/// Two vertices are merged if all their attributes are equal once snapped
+/// to a grid of `tolerance` sized cells. If `tolerance` is zero,
+/// only exactly equal vertices are merged.
+pub(crate) fn weld_vertices(mesh: &Mesh, extra: &[&[[f32; 3]]], tolerance: f32) -> Welded {
+    let vertex_count = mesh.count_vertices();
+    let mut welded = Welded {
+        remap: Vec::with_capacity(vertex_count),
+        kept: Vec::new(),
+    };
+    let mut known: HashMap<Vec<i64>, u32> = HashMap::with_capacity(vertex_count);
+    let mut key = Vec::new();
+    for index in 0..vertex_count {
+        key.clear();
+        for (_, values) in mesh.attributes() {
+            push_key(values, index, tolerance, &mut key);
+        }
+        for values in extra {
+            key.extend(
+                values[index]
+                    .iter()
+                    .map(|&float| float_key(float, tolerance)),
+            );
+        }
+        // Only allocate a new key for vertices we haven't seen yet.
+        let new_index = match known.get(key.as_slice()) {
+            Some(&new_index) => new_index,
+            None => {
+                let new_index = welded.kept.len() as u32;
+                welded.kept.push(index);
+                known.insert(key.clone(), new_index);
+                new_index
+            }
+        };
+        welded.remap.push(new_index);
+    }
+    welded
+}
+
+/// Only keep the vertices at the `kept` indices in the attributes of `mesh`.
+pub(crate) fn select_vertices(mesh: &mut Mesh, kept: &[usize]) {
+    macro_rules! select {
+        ($values:expr, $( $variant:ident ),* $(,)?) => {
+            match $values {
+                $(VertexAttributeValues::$variant(values) => {
+                    *values = kept.iter().map(|&i| values[i]).collect();
+                })*
+            }
+        };
+    }
+    for (_, values) in mesh.attributes_mut() {
+        select!(
+            values, Float32, Sint32, Uint32, Float32x2, Sint32x2, Uint32x2, Float32x3, Sint32x3,
+            Uint32x3, Float32x4, Sint32x4, Uint32x4, Sint16x2, Snorm16x2, Uint16x2, Unorm16x2,
+            Sint16x4, Snorm16x4, Uint16x4, Unorm16x4, Sint8x2, Snorm8x2, Uint8x2, Unorm8x2,
+            Sint8x4, Snorm8x4, Uint8x4, Unorm8x4,
+        );
+    }
+}
+
+/// The vertices used by `indices`, and `indices` remapped to only refer to those.
+///
+/// Use the first returned value with [`select_vertices`] to remove unused vertices,
+/// vertices are kept in the order they are first referenced.
+pub(crate) fn used_vertices(indices: &[u32]) -> (Vec<usize>, Vec<u32>) {
+    let mut kept = Vec::new();
+    let mut known = HashMap::new();
+    let indices = indices
+        .iter()
+        .map(|&index| {
+            *known.entry(index).or_insert_with(|| {
+                kept.push(index as usize);
+                kept.len() as u32 - 1
+            })
+        })
+        .collect();
+    (kept, indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::render::render_resource::PrimitiveTopology;
+
+    use super::*;
+
+    fn mesh(positions: Vec<[f32; 3]>, normals: Vec<[f32; 3]>, uvs: Vec<[f32; 2]>) -> Mesh {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh
+    }
+
+    fn mesh_positions(mesh: &Mesh) -> &[[f32; 3]] {
+        match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(positions)) => positions,
+            _ => panic!("mesh has no positions"),
+        }
+    }
+
+    const UP: [f32; 3] = [0.0, 0.0, 1.0];
+
+    #[test]
+    fn weld_equal_vertices() {
+        let positions = vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [-0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+        ];
+        let mesh = mesh(positions, vec![UP; 4], vec![[0.0, 0.0]; 4]);
+        let welded = weld_vertices(&mesh, &[], 0.0);
+        assert_eq!(welded.remap, [0, 1, 0, 1]);
+        assert_eq!(welded.kept, [0, 1]);
+    }
+
+    #[test]
+    fn weld_within_tolerance() {
+        let positions = vec![[0.0, 0.0, 0.0], [0.001, 0.0, 0.0], [1.0, 0.0, 0.0]];
+        let mesh = mesh(positions, vec![UP; 3], vec![[0.0, 0.0]; 3]);
+
+        let welded = weld_vertices(&mesh, &[], 0.0);
+        assert_eq!(welded.remap, [0, 1, 2]);
+
+        let welded = weld_vertices(&mesh, &[], 0.01);
+        assert_eq!(welded.remap, [0, 0, 1]);
+        assert_eq!(welded.kept, [0, 2]);
+    }
+
+    #[test]
+    fn keep_vertices_with_different_attributes() {
+        let positions = vec![[0.0, 0.0, 0.0]; 3];
+        let normals = vec![UP, UP, [0.0, 1.0, 0.0]];
+        let uvs = vec![[0.0, 0.0], [0.5, 0.0], [0.0, 0.0]];
+        let welded = weld_vertices(&mesh(positions, normals, uvs), &[], 0.01);
+        assert_eq!(welded.remap, [0, 1, 2]);
+
+        // Vertices must also have the same `extra` values.
+        let positions = vec![[0.0, 0.0, 0.0]; 3];
+        let mesh = mesh(positions, vec![UP; 3], vec![[0.0, 0.0]; 3]);
+        let deltas = [[0.0, 0.0, 0.0], [0.0, 0.0, 0.0], [1.0, 0.0, 0.0]];
+        let welded = weld_vertices(&mesh, &[&deltas], 0.0);
+        assert_eq!(welded.remap, [0, 0, 1]);
+    }
+
+    #[test]
+    fn select_welded_vertices() {
+        let positions = vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+        ];
+        let mut mesh = mesh(positions, vec![UP; 4], vec![[0.0, 0.0]; 4]);
+        let welded = weld_vertices(&mesh, &[], 0.0);
+        select_vertices(&mut mesh, &welded.kept);
+        assert_eq!(mesh.count_vertices(), 3);
+        let expected = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+        assert_eq!(mesh_positions(&mesh), expected);
+        // The remapped indices point to the same positions as the original ones.
+        let indices: Vec<_> = [0, 1, 3, 2, 3, 1].map(|i| welded.remap[i]).into();
+        assert_eq!(indices, [0, 1, 2, 0, 2, 1]);
+    }
+
+    #[test]
+    fn remove_unused_vertices() {
+        let (kept, indices) = used_vertices(&[4, 2, 7, 7, 2, 5]);
+        assert_eq!(kept, [4, 2, 7, 5]);
+        assert_eq!(indices, [0, 1, 2, 2, 1, 3]);
+
+        let positions = (0..8).map(|i| [i as f32, 0.0, 0.0]).collect();
+        let mut mesh = mesh(positions, vec![UP; 8], vec![[0.0, 0.0]; 8]);
+        select_vertices(&mut mesh, &kept);
+        let expected = [
+            [4.0, 0.0, 0.0],
+            [2.0, 0.0, 0.0],
+            [7.0, 0.0, 0.0],
+            [5.0, 0.0, 0.0],
+        ];
+        assert_eq!(mesh_positions(&mesh), expected);
+    }
+}
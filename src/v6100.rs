@@ -0,0 +1,660 @@
+//! Load FBX 6.x documents, by converting them to the FBX 7.4 layout
+//! `fbxcel_dom` understands.
+
+// a bit of trivia on how FBX 6.1 differs from FBX 7.4:
+// - The binary encoding of nodes is the same (before 7.5, offsets are 32 bits),
+//   only the way objects are described changes.
+// - Objects have no ids, they are identified by their name, written
+//   `Class::Name` in ASCII files and `Name\0\x01Class` in binary files.
+//   Connections refer to objects by name, the scene root is `Model::Scene`.
+// - Mesh models embed their geometry (`Vertices`, `PolygonVertexIndex`,
+//   `LayerElement`s etc.), and their blend shapes as `Shape` child nodes.
+//   Skin deformers are connected to the model rather than to the geometry.
+// - Camera and light models hold the camera and light properties themselves,
+//   there is no `NodeAttribute`.
+// - Properties are stored as `Property: name, type, flags, values...` in
+//   a `Properties60` node, instead of `P: name, type, label, flags, values...`
+//   in a `Properties70` node.
+// - Textures are connected to models, not to material properties.
+// - `GlobalSettings` is an object, rather than a toplevel node.
+// - Animations are stored in `Takes`, with curves embedded in a copy of each
+//   animated model, they are not supported.
+use std::io::Cursor;
+
+use anyhow::{bail, Context};
+use bevy::utils::{HashMap, HashSet};
+use fbxcel_dom::{
+    fbxcel::{
+        low::{v7400::AttributeValue, FbxHeader},
+        tree::{
+            any::AnyTree,
+            v7400::{NodeHandle, NodeId, Tree},
+        },
+    },
+    v7400::{Document, Loader},
+};
+
+use crate::data::FbxDiagnostic;
+
+/// The name of the scene root object, which always has the id 0 in FBX 7.4.
+const SCENE_ROOT: &str = "Scene\0\x01Model";
+
+/// Children of FBX 6.1 mesh models that belong to the FBX 7.4 `Geometry` object.
+const GEOMETRY_NODES: &[&str] = &[
+    "Vertices",
+    "PolygonVertexIndex",
+    "Edges",
+    "GeometryVersion",
+    "LayerElementNormal",
+    "LayerElementBinormal",
+    "LayerElementTangent",
+    "LayerElementSmoothing",
+    "LayerElementUV",
+    "LayerElementColor",
+    "LayerElementMaterial",
+    "LayerElementTexture",
+    "Layer",
+];
+
+/// FBX 6.1 light properties with a different name in FBX 7.4.
+const LIGHT_PROPERTY_RENAMES: &[(&str, &str)] =
+    &[("Cone angle", "OuterAngle"), ("HotSpot", "InnerAngle")];
+
+/// Default values of the FBX 7.4 `FbxNode` property template.
+///
+/// FBX 6.1 exporters write all the properties of each model, but to be safe,
+/// we provide the defaults of the properties the loader needs.
+const NODE_VECTOR_DEFAULTS: &[(&str, f64)] = &[
+    ("RotationOffset", 0.0),
+    ("RotationPivot", 0.0),
+    ("ScalingOffset", 0.0),
+    ("ScalingPivot", 0.0),
+    ("PreRotation", 0.0),
+    ("PostRotation", 0.0),
+    ("Lcl Translation", 0.0),
+    ("Lcl Rotation", 0.0),
+    ("Lcl Scaling", 1.0),
+    ("GeometricTranslation", 0.0),
+    ("GeometricRotation", 0.0),
+    ("GeometricScaling", 1.0),
+];
+const NODE_ENUM_DEFAULTS: &[&str] = &["RotationOrder", "InheritType"];
+
+/// The offset of the version number in binary FBX files,
+/// after the `Kaydara FBX Binary  \0` magic and two unknown bytes.
+const VERSION_OFFSET: usize = 23;
+
+/// Whether `header` is the header of a FBX 6.x binary file.
+pub(crate) fn is_v6(header: FbxHeader) -> bool {
+    header.version().major() == 6
+}
+
+/// Load a FBX 6.x binary file.
+pub(crate) fn load_binary(
+    bytes: &[u8],
+    diagnostics: &mut Vec<FbxDiagnostic>,
+) -> anyhow::Result<Document> {
+    // fbxcel refuses to parse anything but FBX 7, but nodes are encoded the
+    // same way, so we pretend this is a FBX 7.4 file.
+    let mut bytes = bytes.to_vec();
+    let version = bytes
+        .get_mut(VERSION_OFFSET..VERSION_OFFSET + 4)
+        .context("Truncated FBX header")?;
+    let file_version = u32::from_le_bytes(version.try_into()?);
+    if file_version / 1000 != 6 {
+        bail!("Expected a FBX 6.x header, found version {file_version}");
+    }
+    version.copy_from_slice(&7400_u32.to_le_bytes());
+    let tree = match AnyTree::from_seekable_reader(Cursor::new(bytes))? {
+        AnyTree::V7400(_, tree, _) => tree,
+        _ => bail!("Unknown FBX tree kind"),
+    };
+    load_tree(&tree, diagnostics)
+}
+
+/// Load a FBX 6.1 node tree as a FBX 7.4 document.
+pub(crate) fn load_tree(
+    tree: &Tree,
+    diagnostics: &mut Vec<FbxDiagnostic>,
+) -> anyhow::Result<Document> {
+    let tree = Converter::default().convert(tree, diagnostics)?;
+    Ok(Loader::new().load_from_tree(tree)?)
+}
+
+/// The `Name\0\x01Class` name of an object, whether it was written
+/// `Class::Name` (ASCII files) or `Name\0\x01Class` (binary files).
+pub(crate) fn name_class(name: &str) -> String {
+    match name.split_once("::") {
+        Some((class, name)) if !name.contains("\0\x01") => format!("{name}\0\x01{class}"),
+        _ => name.to_owned(),
+    }
+}
+
+/// The `Class::Name` form of a `Name\0\x01Class` object name, for messages.
+fn display_name(name_class: &str) -> String {
+    match name_class.split_once("\0\x01") {
+        Some((name, class)) => format!("{class}::{name}"),
+        None => name_class.to_owned(),
+    }
+}
+
+/// The class of a `Name\0\x01Class` object name.
+fn class(name_class: &str) -> &str {
+    name_class
+        .split_once("\0\x01")
+        .map_or("", |(_, class)| class)
+}
+
+fn capitalize(value: &str) -> String {
+    let mut chars = value.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn string_attribute(node: NodeHandle<'_>, index: usize) -> Option<&str> {
+    node.attributes().get(index)?.get_string()
+}
+
+/// The indices of the `array` of the `element` layer element of `model`,
+/// and whether they are the same for all polygons (`AllSame` mapping).
+fn layer_indices<'a>(
+    model: NodeHandle<'a>,
+    element: &str,
+    array: &str,
+) -> Option<(bool, &'a [i32])> {
+    let element = model.first_child_by_name(element)?;
+    let mapping = element.first_child_by_name("MappingInformationType")?;
+    let all_same = string_attribute(mapping, 0)? == "AllSame";
+    let indices = element.first_child_by_name(array)?;
+    Some((all_same, indices.attributes().first()?.get_arr_i32()?))
+}
+
+/// The texture index of each material index of a mesh model, the texture
+/// of the first polygon with this material in its `LayerElementTexture`.
+fn material_textures(model: NodeHandle) -> Option<HashMap<usize, usize>> {
+    let (textures_all_same, textures) = layer_indices(model, "LayerElementTexture", "TextureId")?;
+    let materials = layer_indices(model, "LayerElementMaterial", "Materials");
+    let (materials_all_same, materials) = materials.unwrap_or((true, &[0]));
+    let polygon_count = match (materials_all_same, textures_all_same) {
+        (true, true) => 1,
+        (true, false) => textures.len(),
+        (false, true) => materials.len(),
+        (false, false) => materials.len().min(textures.len()),
+    };
+    let index = |indices: &[i32], all_same: bool, polygon: usize| {
+        let index = *indices.get(if all_same { 0 } else { polygon })?;
+        usize::try_from(index).ok()
+    };
+    let mut material_textures = HashMap::new();
+    for polygon in 0..polygon_count {
+        let material = index(materials, materials_all_same, polygon);
+        let texture = index(textures, textures_all_same, polygon);
+        if let (Some(material), Some(texture)) = (material, texture) {
+            material_textures.entry(material).or_insert(texture);
+        }
+    }
+    Some(material_textures)
+}
+
+/// Builds a FBX 7.4 node tree from a FBX 6.1 one.
+#[derive(Default)]
+struct Converter {
+    tree: Tree,
+    /// The FBX 7.4 object id of each FBX 6.1 object, by name.
+    ids: HashMap<String, i64>,
+    /// The id of the `Geometry` created for each mesh model.
+    geometries: HashMap<i64, i64>,
+    /// The texture index of each material index of mesh models,
+    /// read from their `LayerElementTexture`.
+    material_textures: HashMap<i64, HashMap<usize, usize>>,
+    /// The (type, source, destination, property) of each connection.
+    connections: Vec<(&'static str, i64, i64, Option<String>)>,
+    last_id: i64,
+}
+impl Converter {
+    fn new_id(&mut self) -> i64 {
+        self.last_id += 1;
+        self.last_id
+    }
+
+    fn convert(
+        mut self,
+        source: &Tree,
+        diagnostics: &mut Vec<FbxDiagnostic>,
+    ) -> anyhow::Result<Tree> {
+        let root = self.tree.root().node_id();
+        let source_root = source.root();
+        let objects = source_root
+            .first_child_by_name("Objects")
+            .context("No Objects node in FBX file")?;
+
+        if let Some(header) = source_root.first_child_by_name("FBXHeaderExtension") {
+            self.copy_node(root, header);
+        }
+        let global_settings = source_root
+            .first_child_by_name("GlobalSettings")
+            .or_else(|| objects.first_child_by_name("GlobalSettings"));
+        if let Some(global_settings) = global_settings {
+            self.copy_node(root, global_settings);
+        }
+        self.add_node_template(root);
+        let documents = self.tree.append_new(root, "Documents");
+        let count = self.tree.append_new(documents, "Count");
+        self.tree.append_attribute(count, 1_i32);
+        let document = self.tree.append_new(documents, "Document");
+        let document_id = self.new_id();
+        self.tree.set_attributes_vec(
+            document,
+            vec![
+                document_id.into(),
+                String::new().into(),
+                "Scene".to_owned().into(),
+            ],
+        );
+        let root_node = self.tree.append_new(document, "RootNode");
+        self.tree.append_attribute(root_node, 0_i64);
+
+        self.ids.insert(SCENE_ROOT.to_owned(), 0);
+        let new_objects = self.tree.append_new(root, "Objects");
+        for object in objects.children() {
+            if object.name() == "GlobalSettings" {
+                continue;
+            }
+            let Some(name) = string_attribute(object, 0) else {
+                continue;
+            };
+            let name = name_class(name);
+            // Connections refer to objects by name, so we can't tell which
+            // of the objects with the same name they are about.
+            if self.ids.contains_key(&name) {
+                let name = display_name(&name);
+                let message = format!("Several objects are named {name}, only loading the first");
+                FbxDiagnostic::warning(message).report(diagnostics);
+                continue;
+            }
+            let id = self.new_id();
+            self.ids.insert(name.clone(), id);
+            let subclass = string_attribute(object, 1).unwrap_or_default();
+            match (object.name(), subclass) {
+                ("Model", "Mesh") => self.convert_mesh(new_objects, object, id, &name),
+                ("Model", "Camera" | "Light") => {
+                    self.convert_node_attribute(new_objects, object, id, &name, subclass)
+                }
+                _ => {}
+            }
+            let new_object = self.tree.append_new(new_objects, object.name());
+            let attributes = vec![id.into(), name.into(), subclass.to_owned().into()];
+            self.tree.set_attributes_vec(new_object, attributes);
+            for child in object.children() {
+                let is_geometry = GEOMETRY_NODES.contains(&child.name()) || child.name() == "Shape";
+                if !(subclass == "Mesh" && is_geometry) {
+                    self.copy_node(new_object, child);
+                }
+            }
+            if object.name() == "Material" {
+                self.fix_shading_model(new_object, object);
+            }
+        }
+        self.convert_connections(source_root);
+        let connections = self.tree.append_new(root, "Connections");
+        for (kind, source, destination, property) in std::mem::take(&mut self.connections) {
+            let connection = self.tree.append_new(connections, "C");
+            let mut attributes = vec![kind.to_owned().into(), source.into(), destination.into()];
+            attributes.extend(property.map(AttributeValue::from));
+            self.tree.set_attributes_vec(connection, attributes);
+        }
+
+        let takes = source_root.first_child_by_name("Takes");
+        if takes.is_some_and(|takes| takes.children_by_name("Take").next().is_some()) {
+            FbxDiagnostic::warning("Animations of FBX 6 files are not supported, ignoring them")
+                .report(diagnostics);
+        }
+        Ok(self.tree)
+    }
+
+    /// Copy `node` and its descendants as a new child of `parent`,
+    /// converting `Properties60` into `Properties70`.
+    fn copy_node(&mut self, parent: NodeId, node: NodeHandle) -> NodeId {
+        let (name, is_property) = match node.name() {
+            "Properties60" => ("Properties70", false),
+            "Property" => ("P", true),
+            name => (name, false),
+        };
+        let copy = self.tree.append_new(parent, name);
+        let mut attributes = node.attributes().to_vec();
+        if is_property && attributes.len() >= 3 {
+            // The FBX 7.4 label, between the type and the flags.
+            attributes.insert(2, String::new().into());
+        }
+        self.tree.set_attributes_vec(copy, attributes);
+        for child in node.children() {
+            self.copy_node(copy, child);
+        }
+        copy
+    }
+
+    /// Add the `Definitions` node, with the `FbxNode` property template of models.
+    fn add_node_template(&mut self, root: NodeId) {
+        let definitions = self.tree.append_new(root, "Definitions");
+        let object_type = self.tree.append_new(definitions, "ObjectType");
+        self.tree.append_attribute(object_type, "Model".to_owned());
+        let template = self.tree.append_new(object_type, "PropertyTemplate");
+        self.tree.append_attribute(template, "FbxNode".to_owned());
+        let properties = self.tree.append_new(template, "Properties70");
+        let header = |name: &str, kind: &str| -> Vec<AttributeValue> {
+            [name, kind, "", ""].map(|s| s.to_owned().into()).to_vec()
+        };
+        for &(name, value) in NODE_VECTOR_DEFAULTS {
+            let property = self.tree.append_new(properties, "P");
+            let mut attributes = header(name, "Vector3D");
+            attributes.extend([value.into(), value.into(), value.into()]);
+            self.tree.set_attributes_vec(property, attributes);
+        }
+        for &name in NODE_ENUM_DEFAULTS {
+            let property = self.tree.append_new(properties, "P");
+            let mut attributes = header(name, "enum");
+            attributes.push(0_i32.into());
+            self.tree.set_attributes_vec(property, attributes);
+        }
+    }
+
+    /// Add the `Geometry` of a mesh model, and its blend shapes.
+    fn convert_mesh(&mut self, objects: NodeId, model: NodeHandle, model_id: i64, name: &str) {
+        let (name, _) = name.split_once("\0\x01").unwrap_or((name, ""));
+        let geometry_id = self.new_id();
+        let geometry = self.new_object(objects, "Geometry", geometry_id, name, "Mesh");
+        for child in model.children() {
+            if GEOMETRY_NODES.contains(&child.name()) {
+                self.copy_node(geometry, child);
+            }
+        }
+        self.geometries.insert(model_id, geometry_id);
+        self.connections.push(("OO", geometry_id, model_id, None));
+        if let Some(material_textures) = material_textures(model) {
+            self.material_textures.insert(model_id, material_textures);
+        }
+
+        let mut shapes = model.children_by_name("Shape").peekable();
+        if shapes.peek().is_none() {
+            return;
+        }
+        let blend_shape_id = self.new_id();
+        self.new_object(objects, "Deformer", blend_shape_id, name, "BlendShape");
+        self.connections
+            .push(("OO", blend_shape_id, geometry_id, None));
+        for shape in shapes {
+            let shape_name = string_attribute(shape, 0).unwrap_or_default();
+            let channel_id = self.new_id();
+            let channel = self.new_object(
+                objects,
+                "Deformer",
+                channel_id,
+                &format!("{shape_name}\0\x01SubDeformer"),
+                "BlendShapeChannel",
+            );
+            let full_weights = self.tree.append_new(channel, "FullWeights");
+            self.tree.append_attribute(full_weights, vec![100.0_f64]);
+            let shape_id = self.new_id();
+            let geometry = self.new_object(objects, "Geometry", shape_id, shape_name, "Shape");
+            for child in shape.children() {
+                self.copy_node(geometry, child);
+            }
+            self.connections
+                .push(("OO", channel_id, blend_shape_id, None));
+            self.connections.push(("OO", shape_id, channel_id, None));
+        }
+    }
+
+    /// Add the `NodeAttribute` of a camera or light model.
+    fn convert_node_attribute(
+        &mut self,
+        objects: NodeId,
+        model: NodeHandle,
+        model_id: i64,
+        name: &str,
+        subclass: &str,
+    ) {
+        let (name, _) = name.split_once("\0\x01").unwrap_or((name, ""));
+        let id = self.new_id();
+        let attribute = self.new_object(objects, "NodeAttribute", id, name, subclass);
+        if let Some(properties) = model.first_child_by_name("Properties60") {
+            let properties = self.copy_node(attribute, properties);
+            if subclass == "Light" {
+                self.rename_properties(properties, LIGHT_PROPERTY_RENAMES);
+            }
+        }
+        self.connections.push(("OO", id, model_id, None));
+    }
+
+    /// Add an object node named `name` (or `Name\0\x01Class`) of the given `class`.
+    fn new_object(
+        &mut self,
+        objects: NodeId,
+        class: &str,
+        id: i64,
+        name: &str,
+        subclass: &str,
+    ) -> NodeId {
+        let name = if name.contains("\0\x01") {
+            name.to_owned()
+        } else {
+            format!("{name}\0\x01{class}")
+        };
+        let object = self.tree.append_new(objects, class);
+        let attributes = vec![id.into(), name.into(), subclass.to_owned().into()];
+        self.tree.set_attributes_vec(object, attributes);
+        object
+    }
+
+    fn rename_properties(&mut self, properties: NodeId, renames: &[(&str, &str)]) {
+        let children: Vec<_> = properties
+            .to_handle(&self.tree)
+            .children()
+            .map(|child| child.node_id())
+            .collect();
+        for child in children {
+            let Some(AttributeValue::String(name)) = self.tree.get_attribute_mut(child, 0) else {
+                continue;
+            };
+            if let Some((_, new_name)) = renames.iter().find(|(old, _)| old == name) {
+                *name = (*new_name).to_owned();
+            }
+        }
+    }
+
+    /// FBX 7.4 writes the `ShadingModel` property capitalized (`Phong`), FBX 6.1
+    /// exporters may only write it lowercase as a child node (`phong`).
+    fn fix_shading_model(&mut self, material: NodeId, source: NodeHandle) {
+        let properties = material
+            .to_handle(&self.tree)
+            .first_child_by_name("Properties70")
+            .map(|node| node.node_id());
+        let properties = match properties {
+            Some(properties) => properties,
+            None => self.tree.append_new(material, "Properties70"),
+        };
+        let property = properties
+            .to_handle(&self.tree)
+            .children_by_name("P")
+            .find(|p| string_attribute(*p, 0) == Some("ShadingModel"))
+            .map(|p| p.node_id());
+        match property {
+            Some(property) => {
+                if let Some(AttributeValue::String(value)) =
+                    self.tree.get_attribute_mut(property, 4)
+                {
+                    *value = capitalize(value);
+                }
+            }
+            None => {
+                let model = source
+                    .first_child_by_name("ShadingModel")
+                    .and_then(|node| string_attribute(node, 0))
+                    .unwrap_or("Unknown");
+                let property = self.tree.append_new(properties, "P");
+                let attributes = ["ShadingModel", "KString", "", "", &capitalize(model)]
+                    .map(|value| value.to_owned().into())
+                    .to_vec();
+                self.tree.set_attributes_vec(property, attributes);
+            }
+        }
+    }
+
+    /// Convert the name-based connections to id-based connections.
+    ///
+    /// Textures connected to a mesh model are connected to the `DiffuseColor`
+    /// of its materials that don't already have a diffuse texture. The texture
+    /// of a material is the one its polygons use in the `LayerElementTexture`
+    /// of the model. Without `LayerElementTexture`, textures are assigned to
+    /// materials in order, the first texture to materials past the last texture.
+    fn convert_connections(&mut self, source_root: NodeHandle) {
+        let Some(connections) = source_root.first_child_by_name("Connections") else {
+            return;
+        };
+        let mut seen = HashSet::new();
+        let mut model_textures: HashMap<i64, Vec<i64>> = HashMap::new();
+        let mut model_materials: HashMap<i64, Vec<i64>> = HashMap::new();
+        let mut diffuse_textured = HashSet::new();
+        for connection in connections.children_by_name("Connect") {
+            let kind = match string_attribute(connection, 0) {
+                Some("OO") => "OO",
+                Some("OP") => "OP",
+                _ => continue,
+            };
+            let names = (
+                string_attribute(connection, 1),
+                string_attribute(connection, 2),
+            );
+            let (Some(source), Some(destination)) = names else {
+                continue;
+            };
+            let (source, destination) = (name_class(source), name_class(destination));
+            let ids = (self.ids.get(&source), self.ids.get(&destination));
+            let (Some(&source_id), Some(&destination_id)) = ids else {
+                continue;
+            };
+            let property = string_attribute(connection, 3).map(str::to_owned);
+            if !seen.insert((source_id, destination_id, property.clone())) {
+                continue;
+            }
+            let destination_id = match (class(&source), class(&destination)) {
+                ("Texture", "Model") => {
+                    model_textures
+                        .entry(destination_id)
+                        .or_default()
+                        .push(source_id);
+                    continue;
+                }
+                ("Material", "Model") => {
+                    let materials = model_materials.entry(destination_id).or_default();
+                    materials.push(source_id);
+                    destination_id
+                }
+                ("Texture", "Material") if property.as_deref() == Some("DiffuseColor") => {
+                    diffuse_textured.insert(destination_id);
+                    destination_id
+                }
+                // Skins deform the geometry, which is part of the model in FBX 6.
+                ("Deformer", "Model") => match self.geometries.get(&destination_id) {
+                    Some(&geometry_id) => geometry_id,
+                    None => destination_id,
+                },
+                _ => destination_id,
+            };
+            let kind = if property.is_some() { kind } else { "OO" };
+            self.connections
+                .push((kind, source_id, destination_id, property));
+        }
+        for (model, textures) in model_textures {
+            let materials = model_materials.remove(&model).unwrap_or_default();
+            let material_textures = self.material_textures.get(&model);
+            for (i, material) in materials.into_iter().enumerate() {
+                let texture = match material_textures {
+                    Some(material_textures) => material_textures
+                        .get(&i)
+                        .and_then(|&texture| textures.get(texture)),
+                    None => textures.get(i).or(textures.first()),
+                };
+                let Some(&texture) = texture else {
+                    continue;
+                };
+                if !diffuse_textured.insert(material) {
+                    continue;
+                }
+                let property = Some("DiffuseColor".to_owned());
+                self.connections.push(("OP", texture, material, property));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fbxcel_dom::v7400::object::{model::TypedModelHandle, TypedObjectHandle};
+
+    use super::*;
+    use crate::{data::FbxDiagnosticSeverity, utils::fbx_extend::MaterialHandleExt};
+
+    // Generated with `scripts/fbx6_fixture.py`.
+    const BINARY: &[u8] = include_bytes!("../tests/fixtures/v6100_binary.fbx");
+    const ASCII: &[u8] = include_bytes!("../tests/fixtures/v6100_ascii.fbx");
+
+    /// Check the mesh, material and texture wiring of the fixture.
+    fn check_fixture(doc: &Document, diagnostics: &[FbxDiagnostic]) {
+        let mut meshes = doc.objects().filter_map(|obj| match obj.get_typed() {
+            TypedObjectHandle::Model(TypedModelHandle::Mesh(mesh)) => Some(mesh),
+            _ => None,
+        });
+        let mesh = meshes.next().expect("The fixture has a mesh");
+        assert!(meshes.next().is_none());
+        assert_eq!(mesh.name(), Some("Quads"));
+
+        let geometry = mesh.geometry().unwrap();
+        let polygon_vertices = geometry.polygon_vertices().unwrap();
+        assert_eq!(polygon_vertices.raw_control_points().unwrap().count(), 6);
+        assert_eq!(polygon_vertices.raw_polygon_vertices().len(), 8);
+
+        let textures: Vec<_> = mesh
+            .materials()
+            .map(|material| {
+                let texture = material.load_texture("DiffuseColor").unwrap();
+                let video = texture.video_clip().unwrap();
+                assert!(video.content().is_some());
+                (material.name().unwrap(), texture.name().unwrap())
+            })
+            .collect();
+        let expected = [("Red", "RedTexture"), ("Green", "GreenTexture")];
+        assert_eq!(textures, expected);
+
+        let duplicate = diagnostics.iter().find(|diagnostic| {
+            diagnostic.severity == FbxDiagnosticSeverity::Warning
+                && diagnostic.message.contains("Material::Green")
+        });
+        assert!(duplicate.is_some(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn load_binary_fixture() {
+        let mut diagnostics = Vec::new();
+        let doc = load_binary(BINARY, &mut diagnostics).unwrap();
+        check_fixture(&doc, &diagnostics);
+    }
+
+    #[test]
+    fn load_ascii_fixture() {
+        let mut diagnostics = Vec::new();
+        let tree = crate::ascii::parse(ASCII).unwrap();
+        assert_eq!(crate::ascii::version(&tree), Some(6100));
+        let doc = load_tree(&tree, &mut diagnostics).unwrap();
+        check_fixture(&doc, &diagnostics);
+    }
+
+    #[test]
+    fn reject_non_v6_header() {
+        let mut bytes = BINARY.to_vec();
+        bytes[VERSION_OFFSET..VERSION_OFFSET + 4].copy_from_slice(&7400_u32.to_le_bytes());
+        assert!(load_binary(&bytes, &mut Vec::new()).is_err());
+    }
+}